@@ -0,0 +1,183 @@
+use ascending_data::*;
+use serde_json::Value;
+
+#[derive(Clone, Debug)]
+pub enum Command {
+    Validate(Vec<RecordKind>),
+    CheckRefs,
+    ToBin(Vec<RecordKind>, Option<usize>),
+    ToJson(Vec<RecordKind>, Option<usize>),
+    Dump(RecordKind, Option<usize>),
+    Edit(RecordKind, usize, Vec<(String, Value)>),
+    /// Kind to renumber, how, and whether to only list the changes.
+    Renumber(RecordKind, RenumberOp, bool),
+    /// Writes the item schema in use, to be edited.
+    ItemSchema,
+    /// Checks the sprite images against the records using them.
+    AuditSprites,
+}
+
+/// How the records of a kind are given new ids.
+#[derive(Clone, Debug)]
+pub enum RenumberOp {
+    Move {
+        from: usize,
+        through: usize,
+        to: usize,
+    },
+    Swap(usize, usize),
+    Pairs(Vec<(usize, usize)>),
+}
+
+impl Command {
+    pub fn parse(args: &[String]) -> Result<Self, String> {
+        let Some((name, args)) = args.split_first() else {
+            return Err("Missing command".into());
+        };
+
+        match name.as_str() {
+            "validate" => {
+                no_more(args, 1)?;
+                Ok(Command::Validate(kinds(args.first())?))
+            }
+            "check-refs" => {
+                no_more(args, 0)?;
+                Ok(Command::CheckRefs)
+            }
+            "item-schema" => {
+                no_more(args, 0)?;
+                Ok(Command::ItemSchema)
+            }
+            "audit-sprites" => {
+                no_more(args, 0)?;
+                Ok(Command::AuditSprites)
+            }
+            "to-bin" | "to-json" => {
+                no_more(args, 2)?;
+
+                let kinds = kinds(args.first())?;
+                let id = args.get(1).map(|arg| parse_id(arg)).transpose()?;
+
+                if id.is_some() && kinds.len() > 1 {
+                    return Err("A record id needs a kind".into());
+                }
+
+                if name == "to-bin" {
+                    Ok(Command::ToBin(kinds, id))
+                } else {
+                    Ok(Command::ToJson(kinds, id))
+                }
+            }
+            "dump" => {
+                no_more(args, 2)?;
+
+                let kind = RecordKind::parse(args.first().ok_or("Missing kind")?)?;
+                let id = args.get(1).map(|arg| parse_id(arg)).transpose()?;
+
+                Ok(Command::Dump(kind, id))
+            }
+            "edit" => {
+                let kind = RecordKind::parse(args.first().ok_or("Missing kind")?)?;
+                let id = parse_id(args.get(1).ok_or("Missing record id")?)?;
+                let changes = args[2..]
+                    .iter()
+                    .map(|arg| parse_change(arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                if changes.is_empty() {
+                    return Err("Missing field=value".into());
+                }
+
+                Ok(Command::Edit(kind, id, changes))
+            }
+            "move" | "swap" | "renumber" => {
+                let dry_run = args.iter().any(|arg| arg == "--dry-run");
+                let args: Vec<_> = args.iter().filter(|arg| *arg != "--dry-run").collect();
+
+                let kind = RecordKind::parse(args.first().ok_or("Missing kind")?)?;
+                let ids = &args[1..];
+
+                let op = match name.as_str() {
+                    "move" => {
+                        no_more(ids, 2)?;
+
+                        let (from, through) = parse_range(ids.first().ok_or("Missing record id")?)?;
+                        let to = parse_id(ids.get(1).ok_or("Missing target id")?)?;
+
+                        RenumberOp::Move { from, through, to }
+                    }
+                    "swap" => {
+                        no_more(ids, 2)?;
+
+                        let a = parse_id(ids.first().ok_or("Missing record id")?)?;
+                        let b = parse_id(ids.get(1).ok_or("Missing record id")?)?;
+
+                        RenumberOp::Swap(a, b)
+                    }
+                    _ => {
+                        let pairs = ids
+                            .iter()
+                            .map(|arg| parse_pair(arg))
+                            .collect::<Result<Vec<_>, _>>()?;
+
+                        if pairs.is_empty() {
+                            return Err("Missing old:new".into());
+                        }
+
+                        RenumberOp::Pairs(pairs)
+                    }
+                };
+
+                Ok(Command::Renumber(kind, op, dry_run))
+            }
+            _ => Err(format!("Unknown command {}", name)),
+        }
+    }
+}
+
+fn no_more<S: AsRef<str>>(args: &[S], max: usize) -> Result<(), String> {
+    match args.get(max) {
+        Some(arg) => Err(format!("Unexpected argument {}", arg.as_ref())),
+        None => Ok(()),
+    }
+}
+
+fn kinds(arg: Option<&String>) -> Result<Vec<RecordKind>, String> {
+    match arg.map(String::as_str) {
+        None | Some("all") => Ok(RecordKind::ALL.to_vec()),
+        Some(arg) => Ok(vec![RecordKind::parse(arg)?]),
+    }
+}
+
+fn parse_id(arg: &str) -> Result<usize, String> {
+    arg.parse()
+        .map_err(|_| format!("Invalid record id {}", arg))
+}
+
+/// Reads a single id or an inclusive range such as `5-9`.
+fn parse_range(arg: &str) -> Result<(usize, usize), String> {
+    match arg.split_once('-') {
+        Some((from, through)) => Ok((parse_id(from)?, parse_id(through)?)),
+        None => parse_id(arg).map(|id| (id, id)),
+    }
+}
+
+/// Splits `old:new`.
+fn parse_pair(arg: &str) -> Result<(usize, usize), String> {
+    match arg.split_once(':') {
+        Some((old, new)) => Ok((parse_id(old)?, parse_id(new)?)),
+        None => Err(format!("Expected old:new, got {}", arg)),
+    }
+}
+
+/// Splits `field=value`, reading the value as json and falling back to a
+/// plain string so names do not need to be quoted.
+fn parse_change(arg: &str) -> Result<(String, Value), String> {
+    let Some((field, value)) = arg.split_once('=') else {
+        return Err(format!("Expected field=value, got {}", arg));
+    };
+
+    let value = serde_json::from_str(value).unwrap_or_else(|_| Value::String(value.to_owned()));
+
+    Ok((field.to_owned(), value))
+}
@@ -0,0 +1,93 @@
+mod command;
+mod run;
+
+use ascending_data::*;
+use command::*;
+use run::*;
+use std::process::ExitCode;
+
+const USAGE: &str = "Usage: datatool [--project <dir>] [--data <dir>] [--resources <dir>]
+                [--source bin|json] <command>
+
+Commands:
+  validate [kind]                 Checks that the bin file and json copy of
+                                  every record can be read and agree
+  check-refs                      Lists shop items, drops and enemies pointing
+                                  at missing or unnamed records
+  to-bin [kind] [id]              Rebuilds bin files from their json copies
+  to-json [kind] [id]             Rebuilds json copies from the bin files
+  dump <kind> [id]                Prints records as json
+  edit <kind> <id> <field=value>  Sets fields of a record and saves it
+  move <kind> <id[-id]> <to>      Moves records to a new id, shifting the
+                                  records in between
+  swap <kind> <id> <id>           Exchanges the ids of two records
+  renumber <kind> <old:new>...    Gives records new ids, the others keep
+                                  their order and fill the ids left over
+  item-schema                     Writes the built in item data schema to
+                                  item_schema.json, or checks the existing one
+  audit-sprites                   Lists records whose sprite has no image,
+                                  images no record uses and images of an
+                                  unexpected size
+
+Kinds are items, npcs and shops. Commands taking an optional kind work on
+all of them if none is given. Fields are dotted paths such as rgba.r or
+drops.0.shares, values are json and plain text is taken as a string.
+Moving records rewrites the shop items, drops and enemies pointing at
+them, --dry-run lists those changes without saving anything. Sprite
+sizes are set per resources folder as sprite_sizes within project.json,
+folders without one expect the size most of their images share.
+Records are read from their bin files unless --source json is given,
+which reads the json copies the same as the editors do when their
+source is json. to-json always reads the bin files and validate reads
+both, ignoring --source.";
+
+fn main() -> ExitCode {
+    let mut project_args = Vec::new();
+    let mut source = None;
+    let mut args = Vec::new();
+    let mut iter = std::env::args().skip(1);
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--project" | "--data" | "--resources" => {
+                project_args.push(arg);
+                project_args.extend(iter.next());
+            }
+            "--source" => source = Some(iter.next()),
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            _ => args.push(arg),
+        }
+    }
+
+    let result = ProjectPaths::default()
+        .with_args(project_args)
+        .and_then(|project| {
+            let source = match source {
+                Some(Some(arg)) => DataSource::parse(&arg)?,
+                Some(None) => return Err("Missing bin or json after --source".into()),
+                None => DataSource::Bin,
+            };
+
+            Ok((project, source, Command::parse(&args)?))
+        });
+
+    let (project, source, command) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(&project, source, command) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
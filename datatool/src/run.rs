@@ -0,0 +1,266 @@
+use crate::*;
+use serde_json::Value;
+use std::path::Path;
+
+/// Calls a generic function with the record type of a kind.
+macro_rules! with_kind {
+    ($kind:expr, $func:ident($($arg:expr),*)) => {
+        match $kind {
+            RecordKind::Item => $func::<ItemData>($($arg),*),
+            RecordKind::Npc => $func::<NpcData>($($arg),*),
+            RecordKind::Shop => $func::<ShopData>($($arg),*),
+        }
+    };
+}
+
+/// Runs a command, returning false if any record could not be handled.
+/// Records are read from `source`, unless the command is about one kind of
+/// file.
+pub fn run(project: &ProjectPaths, source: DataSource, command: Command) -> Result<bool, String> {
+    let root = project.data.as_path();
+    let mut ok = true;
+
+    match command {
+        Command::Validate(kinds) => {
+            let config = ProjectConfig::load(root)?;
+
+            if kinds.contains(&RecordKind::Item) {
+                if let Err(e) = ItemSchema::load(root) {
+                    println!("{}", e);
+                    ok = false;
+                }
+            }
+
+            for kind in kinds {
+                ok &= with_kind!(kind, validate(root, &config));
+            }
+        }
+        Command::CheckRefs => {
+            let issues = DataSet::load(root, source).check_references();
+
+            for issue in &issues {
+                println!("{}", issue);
+            }
+
+            println!("{} reference problems", issues.len());
+            ok = issues.is_empty();
+        }
+        Command::ToBin(kinds, id) => {
+            for kind in kinds {
+                ok &= with_kind!(kind, to_bin(root, id))?;
+            }
+        }
+        Command::ToJson(kinds, id) => {
+            for kind in kinds {
+                ok &= with_kind!(kind, to_json(root, id))?;
+            }
+        }
+        Command::Dump(kind, id) => ok = with_kind!(kind, dump(root, source, id))?,
+        Command::Edit(kind, id, changes) => with_kind!(kind, edit(root, source, id, &changes))?,
+        Command::Renumber(kind, op, dry_run) => renumber(root, source, kind, op, dry_run)?,
+        Command::ItemSchema => item_schema(root)?,
+        Command::AuditSprites => {
+            let config = ProjectConfig::load(root)?;
+            let audit = AssetAudit::new(&project.resources, &config, &DataSet::load(root, source));
+
+            for line in audit.lines() {
+                println!("{}", line);
+            }
+
+            ok = audit.problems.is_empty();
+        }
+    }
+
+    Ok(ok)
+}
+
+fn ids<T: GameData>(root: &Path, id: Option<usize>) -> Vec<usize> {
+    match id {
+        Some(id) => vec![id],
+        None => (0..T::count(root)).collect(),
+    }
+}
+
+/// Reads every bin file and json copy without changing any of them,
+/// reporting records whose two files disagree.
+fn validate<T: GameData>(root: &Path, config: &ProjectConfig) -> bool {
+    let count = T::count(root);
+    let limit = config.limit::<T>();
+    let mut ok = true;
+
+    println!("{}: {} records", T::FOLDER, count);
+
+    if count > limit {
+        println!("  more records than the project limit of {}", limit);
+        ok = false;
+    }
+
+    for id in 0..count {
+        let mut bin = None;
+        let mut json = None;
+
+        if T::bin_path(root, id).exists() {
+            match T::load_file(root, id) {
+                Ok((data, migrated)) => {
+                    if migrated {
+                        println!("  {} {}: uses an older layout", T::NAME, id);
+                    }
+
+                    bin = Some(data);
+                }
+                Err(e) => {
+                    println!("  {} {}: {}", T::NAME, id, e);
+                    ok = false;
+                }
+            }
+        } else {
+            println!("  {} {}: bin file is missing", T::NAME, id);
+            ok = false;
+        }
+
+        if T::json_path(root, id).exists() {
+            match T::load_json_file(root, id) {
+                Ok(data) => json = Some(data),
+                Err(e) => {
+                    println!("  {} {}: {}", T::NAME, id, e);
+                    ok = false;
+                }
+            }
+        }
+
+        if let (Some(bin), Some(json)) = (&bin, &json) {
+            if let Some(conflict) = T::find_conflict(id, bin, json) {
+                println!("  {}", conflict);
+                ok = false;
+            }
+        }
+    }
+
+    ok
+}
+
+fn to_bin<T: GameData>(root: &Path, id: Option<usize>) -> Result<bool, String> {
+    let mut ok = true;
+
+    for id in ids::<T>(root, id) {
+        match T::load_json_file(root, id) {
+            Ok(data) => data.save_bin_file(root, id)?,
+            Err(e) => {
+                eprintln!("{} {}: {}", T::NAME, id, e);
+                ok = false;
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+fn to_json<T: GameData>(root: &Path, id: Option<usize>) -> Result<bool, String> {
+    let mut ok = true;
+
+    for id in ids::<T>(root, id) {
+        match T::load_file(root, id) {
+            Ok((data, _)) => data.save_file(root, id)?,
+            Err(e) => {
+                eprintln!("{} {}: {}", T::NAME, id, e);
+                ok = false;
+            }
+        }
+    }
+
+    Ok(ok)
+}
+
+/// Prints a single record as a json object, or all of them as an array
+/// indexed by record id.
+fn dump<T: GameData>(root: &Path, source: DataSource, id: Option<usize>) -> Result<bool, String> {
+    let mut records = Vec::new();
+
+    for id in ids::<T>(root, id) {
+        records.push(T::load_record(root, id, source)?);
+    }
+
+    let output = match id {
+        Some(_) => serde_json::to_string_pretty(&records[0]),
+        None => serde_json::to_string_pretty(&records),
+    };
+
+    match output {
+        Ok(text) => println!("{}", text),
+        Err(e) => return Err(format!("Serdes Error {:?}", e)),
+    }
+
+    Ok(true)
+}
+
+/// Applies field changes to a record and saves the bin file and json copy.
+fn edit<T: GameData>(
+    root: &Path,
+    source: DataSource,
+    id: usize,
+    changes: &[(String, Value)],
+) -> Result<(), String> {
+    if id >= T::count(root) {
+        return Err(format!("{} {} does not exist", T::NAME, id));
+    }
+
+    let mut data = T::load_record(root, id, source)?;
+
+    for (field, value) in changes {
+        data = set_field(&data, field, value.clone())?;
+    }
+
+    data.save(root, id, true)?;
+    println!("Saved {} {}", T::NAME, id);
+    Ok(())
+}
+
+/// Writes the built in item schema into the data folder to be edited,
+/// unless the project has one already.
+fn item_schema(root: &Path) -> Result<(), String> {
+    let path = ItemSchema::path(root);
+
+    if path.exists() {
+        ItemSchema::load(root)?;
+        println!("{} exists and is valid", path.display());
+        return Ok(());
+    }
+
+    ItemSchema::default().save(root)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+fn count<T: GameData>(root: &Path) -> usize {
+    T::count(root)
+}
+
+/// Gives records new ids and rewrites every reference to them, listing the
+/// changes. With `dry_run` nothing is saved.
+fn renumber(
+    root: &Path,
+    source: DataSource,
+    kind: RecordKind,
+    op: RenumberOp,
+    dry_run: bool,
+) -> Result<(), String> {
+    let count = with_kind!(kind, count(root));
+    let renumber = match op {
+        RenumberOp::Move { from, through, to } => Renumber::moved(kind, count, from, through, to),
+        RenumberOp::Swap(a, b) => Renumber::swapped(kind, count, a, b),
+        RenumberOp::Pairs(pairs) => Renumber::new(kind, count, &pairs),
+    }?;
+    let plan = RenumberPlan::new(DataSet::load_checked(root, source)?, renumber)?;
+
+    for change in &plan.changes {
+        println!("{}", change);
+    }
+
+    if dry_run {
+        println!("{} changes, nothing was saved", plan.changes.len());
+    } else {
+        println!("Saved {} records", plan.apply(root, true)?);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,317 @@
+use crate::*;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt, fs,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// A folder of sprites within the resources of a project.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SpriteFolder {
+    pub folder: &'static str,
+    /// File name of a sprite before its id, such as `i` for `i12.png`.
+    pub prefix: &'static str,
+    /// Kind of the records whose `sprite` points into the folder. Nothing
+    /// within the project points at the images of folders without one.
+    pub kind: Option<RecordKind>,
+}
+
+impl SpriteFolder {
+    pub const ITEMS: SpriteFolder = SpriteFolder {
+        folder: "items",
+        prefix: "i",
+        kind: Some(RecordKind::Item),
+    };
+    pub const NPCS: SpriteFolder = SpriteFolder {
+        folder: "npc",
+        prefix: "p",
+        kind: Some(RecordKind::Npc),
+    };
+    pub const RESOURCES: SpriteFolder = SpriteFolder {
+        folder: "resource",
+        prefix: "",
+        kind: None,
+    };
+    pub const ALL: [SpriteFolder; 3] = [Self::ITEMS, Self::NPCS, Self::RESOURCES];
+
+    pub fn file_name(&self, sprite: i64) -> String {
+        format!("{}/{}{}.png", self.folder, self.prefix, sprite)
+    }
+
+    /// Path of a sprite within the resources folder of a project.
+    pub fn path(&self, resources: &Path, sprite: i64) -> PathBuf {
+        resources
+            .join(self.folder)
+            .join(format!("{}{}.png", self.prefix, sprite))
+    }
+
+    /// Ids of every sprite within the resources folder of a project, in
+    /// order. Files not named like a sprite are skipped.
+    pub fn sprite_ids(&self, resources: &Path) -> Vec<i64> {
+        let Ok(entries) = fs::read_dir(resources.join(self.folder)) else {
+            return Vec::new();
+        };
+
+        let mut ids: Vec<i64> = entries
+            .filter_map(|entry| self.sprite_of(entry.ok()?.file_name().to_str()?))
+            .collect();
+
+        ids.sort_unstable();
+        ids
+    }
+
+    /// Sprite id of a file name, if it is named like a sprite.
+    pub fn sprite_of(&self, name: &str) -> Option<i64> {
+        name.strip_prefix(self.prefix)?
+            .strip_suffix(".png")?
+            .parse()
+            .ok()
+    }
+}
+
+/// Width and height of a png image, read from its header.
+pub fn png_size(path: &Path) -> Result<(u32, u32), String> {
+    let mut header = [0u8; 24];
+    let mut file = fs::File::open(path).map_err(|e| format!("can not be read, Err {:?}", e))?;
+
+    if file.read_exact(&mut header).is_err()
+        || header[..8] != *b"\x89PNG\r\n\x1a\n"
+        || header[12..16] != *b"IHDR"
+    {
+        return Err("is not a png image".into());
+    }
+
+    let width = u32::from_be_bytes([header[16], header[17], header[18], header[19]]);
+    let height = u32::from_be_bytes([header[20], header[21], header[22], header[23]]);
+
+    Ok((width, height))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AssetProblem {
+    /// A record points at a sprite without an image.
+    Missing {
+        owner: RecordRef,
+        file: String,
+    },
+    /// An image no record points at, or one not named like a sprite.
+    Unused {
+        file: String,
+        kind: RecordKind,
+    },
+    /// An image of another size than the folder expects.
+    Size {
+        file: String,
+        size: (u32, u32),
+        expected: (u32, u32),
+    },
+    Unreadable {
+        file: String,
+        error: String,
+    },
+}
+
+impl AssetProblem {
+    /// The record that needs fixing, if the problem lies with one.
+    pub fn owner(&self) -> Option<RecordRef> {
+        match self {
+            AssetProblem::Missing { owner, .. } => Some(*owner),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AssetProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssetProblem::Missing { owner, file } => {
+                write!(f, "{} sprite: {} does not exist", owner, file)
+            }
+            AssetProblem::Unused { file, kind } => {
+                write!(f, "{} is not the sprite of any {}", file, kind)
+            }
+            AssetProblem::Size {
+                file,
+                size,
+                expected,
+            } => write!(
+                f,
+                "{} is {}x{}, expected {}x{}",
+                file, size.0, size.1, expected.0, expected.1
+            ),
+            AssetProblem::Unreadable { file, error } => write!(f, "{} {}", file, error),
+        }
+    }
+}
+
+/// Sprite images of a project checked against the records: sprites records
+/// point at without an image, images nothing points at and images whose
+/// size differs from the rest of their folder.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AssetAudit {
+    pub problems: Vec<AssetProblem>,
+    /// Amount of images checked.
+    pub images: usize,
+    /// Folders whose images are only checked for their size, as no record
+    /// points into them.
+    pub unowned: Vec<&'static str>,
+}
+
+impl AssetAudit {
+    /// Checks every sprite folder. Records without a name are skipped, they
+    /// were never filled in.
+    pub fn new(resources: &Path, config: &ProjectConfig, data: &DataSet) -> Self {
+        let mut audit = Self::default();
+
+        for folder in SpriteFolder::ALL {
+            let used: BTreeMap<i64, Vec<usize>> = match folder.kind {
+                Some(RecordKind::Item) => Self::sprites(
+                    data.items
+                        .iter()
+                        .map(|item| (item.name.as_str(), i64::from(item.sprite))),
+                ),
+                Some(RecordKind::Npc) => Self::sprites(
+                    data.npcs
+                        .iter()
+                        .map(|npc| (npc.name.as_str(), i64::from(npc.sprite))),
+                ),
+                Some(RecordKind::Shop) | None => {
+                    audit.unowned.push(folder.folder);
+                    BTreeMap::new()
+                }
+            };
+
+            audit.check_folder(resources, folder, config, &used);
+        }
+
+        audit
+    }
+
+    /// Records using each sprite.
+    fn sprites<'a>(records: impl Iterator<Item = (&'a str, i64)>) -> BTreeMap<i64, Vec<usize>> {
+        let mut used: BTreeMap<i64, Vec<usize>> = BTreeMap::new();
+
+        for (id, (name, sprite)) in records.enumerate() {
+            if !name.is_empty() {
+                used.entry(sprite).or_default().push(id);
+            }
+        }
+
+        used
+    }
+
+    fn check_folder(
+        &mut self,
+        resources: &Path,
+        folder: SpriteFolder,
+        config: &ProjectConfig,
+        used: &BTreeMap<i64, Vec<usize>>,
+    ) {
+        let mut names: Vec<String> = fs::read_dir(resources.join(folder.folder))
+            .into_iter()
+            .flatten()
+            .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+            .filter(|name| name.ends_with(".png"))
+            .collect();
+        names.sort_by_key(|name| (folder.sprite_of(name), name.clone()));
+
+        let mut found = BTreeSet::new();
+        let mut sizes = Vec::new();
+
+        for name in names {
+            let file = format!("{}/{}", folder.folder, name);
+            let sprite = folder.sprite_of(&name);
+
+            self.images += 1;
+            found.extend(sprite);
+
+            if let Some(kind) = folder.kind {
+                if !sprite.is_some_and(|sprite| used.contains_key(&sprite)) {
+                    self.problems.push(AssetProblem::Unused {
+                        file: file.clone(),
+                        kind,
+                    });
+                }
+            }
+
+            match png_size(&resources.join(folder.folder).join(&name)) {
+                Ok(size) => sizes.push((file, size)),
+                Err(error) => self.problems.push(AssetProblem::Unreadable { file, error }),
+            }
+        }
+
+        if let Some(kind) = folder.kind {
+            for (sprite, owners) in used.iter() {
+                if found.contains(sprite) {
+                    continue;
+                }
+
+                for id in owners {
+                    self.problems.push(AssetProblem::Missing {
+                        owner: RecordRef::new(kind, *id),
+                        file: folder.file_name(*sprite),
+                    });
+                }
+            }
+        }
+
+        let Some(expected) = config
+            .sprite_size(folder.folder)
+            .or_else(|| most_common(sizes.iter().map(|(_, size)| *size)))
+        else {
+            return;
+        };
+
+        for (file, size) in sizes {
+            if size != expected {
+                self.problems.push(AssetProblem::Size {
+                    file,
+                    size,
+                    expected,
+                });
+            }
+        }
+    }
+
+    /// Notes on the folders that could not be checked fully.
+    pub fn notes(&self) -> Vec<String> {
+        self.unowned
+            .iter()
+            .map(|folder| {
+                format!(
+                    "{} images are not the sprite of any record, only their size was checked",
+                    folder
+                )
+            })
+            .collect()
+    }
+
+    /// Lines of the report, ending with a summary.
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines: Vec<String> = self.problems.iter().map(ToString::to_string).collect();
+
+        lines.extend(self.notes());
+        lines.push(format!(
+            "{} images checked, {} sprite problems",
+            self.images,
+            self.problems.len()
+        ));
+        lines
+    }
+}
+
+/// The size most images share, the smallest one on a tie.
+fn most_common(sizes: impl Iterator<Item = (u32, u32)>) -> Option<(u32, u32)> {
+    let mut counts: BTreeMap<(u32, u32), usize> = BTreeMap::new();
+
+    for size in sizes {
+        *counts.entry(size).or_default() += 1;
+    }
+
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(size, _)| size)
+}
@@ -0,0 +1,359 @@
+use crate::*;
+use serde_json::Value;
+
+/// Dotted paths of the numeric and boolean fields of a record type, the
+/// fields a bulk edit can change. Array entries are listed one by one, such
+/// as `drops.0.shares`.
+pub fn bulk_fields<T: GameData>() -> Vec<String> {
+    let mut fields = Vec::new();
+
+    if let Ok(json) = serde_json::to_value(T::default()) {
+        collect_fields(String::new(), &json, &mut fields);
+    }
+
+    fields
+}
+
+fn collect_fields(path: String, value: &Value, out: &mut Vec<String>) {
+    let join = |key: &dyn std::fmt::Display| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields {
+                collect_fields(join(key), value, out);
+            }
+        }
+        Value::Array(entries) => {
+            for (i, value) in entries.iter().enumerate() {
+                collect_fields(join(&i), value, out);
+            }
+        }
+        Value::Number(_) | Value::Bool(_) => out.push(path),
+        _ => {}
+    }
+}
+
+/// Arithmetic on the current value of a field, named `x`, such as
+/// `x * 1.1` or `(x + 5) / 2`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Value,
+    Number(f64),
+    Negate(Box<Expr>),
+    Add(Box<Expr>, Box<Expr>),
+    Subtract(Box<Expr>, Box<Expr>),
+    Multiply(Box<Expr>, Box<Expr>),
+    Divide(Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let tokens: Vec<char> = input.chars().filter(|c| !c.is_whitespace()).collect();
+        let mut pos = 0;
+        let expr = Self::sum(&tokens, &mut pos)?;
+
+        match tokens.get(pos) {
+            None => Ok(expr),
+            Some(c) => Err(format!("Unexpected {} within the expression", c)),
+        }
+    }
+
+    fn sum(tokens: &[char], pos: &mut usize) -> Result<Self, String> {
+        let mut expr = Self::product(tokens, pos)?;
+
+        while let Some(op @ ('+' | '-')) = tokens.get(*pos) {
+            *pos += 1;
+            let right = Box::new(Self::product(tokens, pos)?);
+
+            expr = match op {
+                '+' => Expr::Add(Box::new(expr), right),
+                _ => Expr::Subtract(Box::new(expr), right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn product(tokens: &[char], pos: &mut usize) -> Result<Self, String> {
+        let mut expr = Self::factor(tokens, pos)?;
+
+        while let Some(op @ ('*' | '/')) = tokens.get(*pos) {
+            *pos += 1;
+            let right = Box::new(Self::factor(tokens, pos)?);
+
+            expr = match op {
+                '*' => Expr::Multiply(Box::new(expr), right),
+                _ => Expr::Divide(Box::new(expr), right),
+            };
+        }
+
+        Ok(expr)
+    }
+
+    fn factor(tokens: &[char], pos: &mut usize) -> Result<Self, String> {
+        match tokens.get(*pos) {
+            Some('-') => {
+                *pos += 1;
+                Ok(Expr::Negate(Box::new(Self::factor(tokens, pos)?)))
+            }
+            Some('x' | 'X') => {
+                *pos += 1;
+                Ok(Expr::Value)
+            }
+            Some('(') => {
+                *pos += 1;
+                let expr = Self::sum(tokens, pos)?;
+
+                if tokens.get(*pos) != Some(&')') {
+                    return Err("Missing ) within the expression".into());
+                }
+
+                *pos += 1;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || *c == '.' => {
+                let start = *pos;
+
+                while tokens
+                    .get(*pos)
+                    .is_some_and(|c| c.is_ascii_digit() || *c == '.')
+                {
+                    *pos += 1;
+                }
+
+                let number: String = tokens[start..*pos].iter().collect();
+
+                number
+                    .parse()
+                    .map(Expr::Number)
+                    .map_err(|_| format!("{} is not a number", number))
+            }
+            Some(c) => Err(format!("Unexpected {} within the expression", c)),
+            None => Err("The expression ends too early".into()),
+        }
+    }
+
+    /// Works the expression out for a current value of `x`.
+    pub fn eval(&self, x: f64) -> f64 {
+        match self {
+            Expr::Value => x,
+            Expr::Number(number) => *number,
+            Expr::Negate(expr) => -expr.eval(x),
+            Expr::Add(left, right) => left.eval(x) + right.eval(x),
+            Expr::Subtract(left, right) => left.eval(x) - right.eval(x),
+            Expr::Multiply(left, right) => left.eval(x) * right.eval(x),
+            Expr::Divide(left, right) => left.eval(x) / right.eval(x),
+        }
+    }
+}
+
+/// Change made to one field of every selected record.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BulkOp {
+    /// Sets the field to a number or boolean.
+    Set(Value),
+    /// Works the new value out from the current one. Results are rounded
+    /// for whole number fields.
+    Expression(Expr),
+}
+
+impl BulkOp {
+    pub fn set(input: &str) -> Result<Self, String> {
+        match serde_json::from_str(input.trim()) {
+            Ok(value @ (Value::Number(_) | Value::Bool(_))) => Ok(BulkOp::Set(value)),
+            _ => Err(format!("{} is not a number or boolean", input.trim())),
+        }
+    }
+
+    pub fn expression(input: &str) -> Result<Self, String> {
+        Expr::parse(input).map(BulkOp::Expression)
+    }
+
+    /// New value of a field currently holding `value`.
+    pub fn apply(&self, value: &Value) -> Result<Value, String> {
+        match self {
+            BulkOp::Set(new) => Ok(new.clone()),
+            BulkOp::Expression(expr) => {
+                let x = value
+                    .as_f64()
+                    .ok_or_else(|| format!("{} is not a number", value))?;
+                let result = expr.eval(x);
+
+                if !result.is_finite() {
+                    return Err(format!("{} works out to {}", x, result));
+                }
+
+                if value.is_f64() {
+                    Ok(Value::from(result))
+                } else {
+                    Ok(Value::from(result.round() as i64))
+                }
+            }
+        }
+    }
+}
+
+/// Record changed by a bulk edit, with the field value before and after.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkChange<T> {
+    pub id: usize,
+    pub before: T,
+    pub after: T,
+    pub old: Value,
+    pub new: Value,
+}
+
+/// A bulk edit worked out for a selection of records without touching
+/// them, to be previewed before it is applied. Records the edit leaves as
+/// they are are left out.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulkPlan<T> {
+    pub field: String,
+    pub changes: Vec<BulkChange<T>>,
+    /// Records the edit could not be made to, such as a value that does not
+    /// fit the field.
+    pub errors: Vec<String>,
+}
+
+impl<T: GameData + PartialEq> BulkPlan<T> {
+    pub fn new<'a>(
+        field: &str,
+        op: &BulkOp,
+        records: impl IntoIterator<Item = (usize, &'a T)>,
+    ) -> Self
+    where
+        T: 'a,
+    {
+        let mut plan = Self {
+            field: field.to_owned(),
+            changes: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        for (id, record) in records {
+            match Self::change(field, op, id, record) {
+                Ok(Some(change)) => plan.changes.push(change),
+                Ok(None) => {}
+                Err(e) => plan.errors.push(format!("{} {}: {}", T::NAME, id, e)),
+            }
+        }
+
+        plan
+    }
+
+    fn change(
+        field: &str,
+        op: &BulkOp,
+        id: usize,
+        record: &T,
+    ) -> Result<Option<BulkChange<T>>, String> {
+        let json = serde_json::to_value(record).map_err(|e| format!("Serdes Error {:?}", e))?;
+        let old = get_field(&json, field)
+            .cloned()
+            .ok_or_else(|| format!("{} has no field {}", T::NAME, field))?;
+        let new = op.apply(&old)?;
+
+        if new == old {
+            return Ok(None);
+        }
+
+        Ok(Some(BulkChange {
+            id,
+            before: record.clone(),
+            after: set_field(record, field, new.clone())?,
+            old,
+            new,
+        }))
+    }
+
+    /// Whether every changed record still holds what the plan was worked out
+    /// from.
+    pub fn is_current(&self, data: &[(T, bool)]) -> bool {
+        self.changes.iter().all(|change| {
+            data.get(change.id)
+                .is_some_and(|(record, _)| *record == change.before)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(input: &str, x: f64) -> f64 {
+        Expr::parse(input).unwrap().eval(x)
+    }
+
+    #[test]
+    fn parse_follows_precedence() {
+        assert_eq!(eval("x * 1.5", 10.0), 15.0);
+        assert_eq!(eval("x + 2 * 3", 1.0), 7.0);
+        assert_eq!(eval("(x + 2) * 3", 1.0), 9.0);
+        assert_eq!(eval("x - 2 - 3", 10.0), 5.0);
+        assert_eq!(eval("x / 2 / 5", 100.0), 10.0);
+        assert_eq!(eval("-x + 4", 1.0), 3.0);
+        assert_eq!(eval(" X*--2 ", 3.0), 6.0);
+    }
+
+    #[test]
+    fn parse_rejects_broken_expressions() {
+        for input in ["", "x +", "(x + 1", "x + 1)", "x y", "1.2.3", "x % 2"] {
+            assert!(Expr::parse(input).is_err(), "{} parsed", input);
+        }
+    }
+
+    #[test]
+    fn set_takes_numbers_and_booleans() {
+        assert_eq!(BulkOp::set(" 5 "), Ok(BulkOp::Set(Value::from(5))));
+        assert_eq!(BulkOp::set("true"), Ok(BulkOp::Set(Value::from(true))));
+        assert!(BulkOp::set("\"text\"").is_err());
+        assert!(BulkOp::set("five").is_err());
+    }
+
+    #[test]
+    fn apply_rounds_whole_number_fields() {
+        let op = BulkOp::expression("x * 1.1").unwrap();
+
+        assert_eq!(op.apply(&Value::from(15)), Ok(Value::from(17)));
+        assert_eq!(op.apply(&Value::from(-15)), Ok(Value::from(-17)));
+        assert_eq!(op.apply(&Value::from(2.0)), Ok(Value::from(2.2)));
+    }
+
+    #[test]
+    fn apply_rejects_bad_values() {
+        let op = BulkOp::expression("x / 0").unwrap();
+
+        assert!(op.apply(&Value::from(1)).is_err());
+        assert!(op.apply(&Value::from(true)).is_err());
+        assert_eq!(
+            BulkOp::set("true").unwrap().apply(&Value::from(false)),
+            Ok(Value::from(true))
+        );
+    }
+
+    #[test]
+    fn plan_skips_unchanged_records() {
+        let items = [
+            ItemData {
+                baseprice: 100,
+                ..Default::default()
+            },
+            ItemData::default(),
+        ];
+        let op = BulkOp::expression("x * 2").unwrap();
+        let plan = BulkPlan::new("baseprice", &op, items.iter().enumerate());
+
+        assert_eq!(plan.changes.len(), 1);
+        assert_eq!(plan.changes[0].after.baseprice, 200);
+        assert!(plan.errors.is_empty());
+        assert!(!BulkPlan::new("nothing", &op, items.iter().enumerate())
+            .errors
+            .is_empty());
+    }
+}
@@ -0,0 +1,37 @@
+use crate::*;
+use serde_json::{json, Value};
+
+/// Turns a record into the json put on the clipboard when copying it. The
+/// record is tagged with the folder of its kind, so it can not be pasted
+/// into an editor of another kind by accident.
+pub fn copy_record<T: GameData>(record: &T) -> Result<String, String> {
+    let record = serde_json::to_value(record).map_err(|e| format!("Serdes Error {:?}", e))?;
+
+    serde_json::to_string_pretty(&json!({ "kind": T::FOLDER, "record": record }))
+        .map_err(|e| format!("Serdes Error {:?}", e))
+}
+
+/// Reads a record copied with `copy_record`. A bare record, such as the
+/// contents of a json copy, is accepted as well.
+pub fn paste_record<T: GameData>(text: &str) -> Result<T, String> {
+    let value: Value = serde_json::from_str(text)
+        .map_err(|_| "The clipboard does not hold a record".to_owned())?;
+
+    let record = match (value.get("kind"), value.get("record")) {
+        (Some(kind), Some(record)) => {
+            if kind.as_str() != Some(T::FOLDER) {
+                return Err(format!(
+                    "The clipboard holds {} rather than {}",
+                    kind.as_str().unwrap_or("an unknown kind"),
+                    T::FOLDER
+                ));
+            }
+
+            record.clone()
+        }
+        _ => value,
+    };
+
+    serde_json::from_value(record)
+        .map_err(|e| format!("The clipboard is not a valid {}, {}", T::NAME, e))
+}
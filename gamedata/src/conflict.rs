@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+
+/// Which file of a record is treated as the source of truth.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DataSource {
+    /// Records are read from their bin files, json copies are only written.
+    #[default]
+    Bin,
+    /// Records are read from their json copies so hand edits are picked up,
+    /// bin files are rewritten from them on save.
+    Json,
+}
+
+impl DataSource {
+    /// Parses a source by its name, `bin` or `json`.
+    pub fn parse(arg: &str) -> Result<Self, String> {
+        match arg {
+            "bin" => Ok(DataSource::Bin),
+            "json" => Ok(DataSource::Json),
+            _ => Err(format!("Unknown source {}, expected bin or json", arg)),
+        }
+    }
+}
+
+impl fmt::Display for DataSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataSource::Bin => f.write_str("bin"),
+            DataSource::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// A field whose value differs between the bin file and json copy.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldDifference {
+    pub field: String,
+    pub bin: Value,
+    pub json: Value,
+}
+
+/// A record whose bin file and json copy both load but disagree.
+#[derive(Clone, Debug)]
+pub struct Conflict {
+    pub name: &'static str,
+    pub id: usize,
+    pub differences: Vec<FieldDifference>,
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: bin and json differ in {}",
+            self.name,
+            self.id,
+            self.differences
+                .iter()
+                .map(|difference| difference.field.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
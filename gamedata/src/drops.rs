@@ -0,0 +1,281 @@
+use crate::*;
+#[cfg(feature = "simulation")]
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// Chance of a drop slot being rolled when its npc is killed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DropChance {
+    pub slot: usize,
+    pub shares: u32,
+    pub chance: f64,
+}
+
+/// Drop rolls of an npc. Every kill rolls a single drop slot weighted by
+/// its shares, `free_shares` being the weight of dropping nothing, and
+/// drops every item of that slot with an amount.
+impl NpcData {
+    /// Shares of every drop slot plus the free shares.
+    pub fn total_shares(&self) -> u64 {
+        self.drops
+            .iter()
+            .map(|drop| u64::from(drop.shares))
+            .sum::<u64>()
+            + u64::from(self.free_shares)
+    }
+
+    /// Chance of each drop slot that has shares.
+    pub fn drop_chances(&self) -> Vec<DropChance> {
+        let total = self.total_shares();
+
+        self.drops
+            .iter()
+            .enumerate()
+            .filter(|(_, drop)| drop.shares > 0)
+            .map(|(slot, drop)| DropChance {
+                slot,
+                shares: drop.shares,
+                chance: drop.shares as f64 / total as f64,
+            })
+            .collect()
+    }
+
+    /// Chance of a kill dropping nothing.
+    pub fn nothing_chance(&self) -> f64 {
+        match self.total_shares() {
+            0 => 1.0,
+            total => f64::from(self.free_shares) / total as f64,
+        }
+    }
+
+    /// Average amount of each item dropped per kill.
+    pub fn expected_drops(&self) -> BTreeMap<u32, f64> {
+        let mut drops = BTreeMap::new();
+
+        for chance in self.drop_chances() {
+            for item in self.drops[chance.slot].items.iter() {
+                if item.amount > 0 {
+                    *drops.entry(item.item).or_default() += chance.chance * f64::from(item.amount);
+                }
+            }
+        }
+
+        drops
+    }
+
+    /// Rolls the drop slot of a single kill, None when nothing drops.
+    #[cfg(feature = "simulation")]
+    pub fn roll_drop(&self, rng: &mut impl Rng) -> Option<usize> {
+        let total = self.total_shares();
+
+        if total == 0 {
+            return None;
+        }
+
+        let mut roll = rng.gen_range(0..total);
+
+        for (slot, drop) in self.drops.iter().enumerate() {
+            let shares = u64::from(drop.shares);
+
+            if roll < shares {
+                return Some(slot);
+            }
+
+            roll -= shares;
+        }
+
+        None
+    }
+}
+
+/// Outcome of rolling the drops of many kills of an npc.
+#[cfg(feature = "simulation")]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DropSimulation {
+    pub kills: u64,
+    /// How often each drop slot was rolled.
+    pub slots: [u64; 10],
+    /// Total amount dropped of each item.
+    pub items: BTreeMap<u32, u64>,
+}
+
+#[cfg(feature = "simulation")]
+impl DropSimulation {
+    pub fn run(npc: &NpcData, kills: u64, rng: &mut impl Rng) -> Self {
+        let mut simulation = Self {
+            kills,
+            ..Default::default()
+        };
+
+        for _ in 0..kills {
+            let Some(slot) = npc.roll_drop(rng) else {
+                continue;
+            };
+
+            simulation.slots[slot] += 1;
+
+            for item in npc.drops[slot].items.iter() {
+                if item.amount > 0 {
+                    *simulation.items.entry(item.item).or_default() += u64::from(item.amount);
+                }
+            }
+        }
+
+        simulation
+    }
+
+    /// Kills that dropped nothing.
+    pub fn nothing(&self) -> u64 {
+        self.kills - self.slots.iter().sum::<u64>()
+    }
+
+    /// Spreads a total over the simulated kills.
+    pub fn per_kill(&self, total: u64) -> f64 {
+        match self.kills {
+            0 => 0.0,
+            kills => total as f64 / kills as f64,
+        }
+    }
+}
+
+/// Gold value of dropped items by their base price. Items that do not exist
+/// are worth nothing.
+pub fn drop_value(drops: impl IntoIterator<Item = (u32, f64)>, items: &[ItemData]) -> f64 {
+    drops
+        .into_iter()
+        .map(|(item, amount)| {
+            let price = items
+                .get(item as usize)
+                .map_or(0.0, |data| data.baseprice as f64);
+
+            amount * price
+        })
+        .fold(0.0, |total, value| total + value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn npc_with(shares: &[u32], free_shares: u32) -> NpcData {
+        let mut npc = NpcData {
+            free_shares,
+            ..Default::default()
+        };
+
+        for (slot, shares) in shares.iter().enumerate() {
+            npc.drops[slot].shares = *shares;
+            npc.drops[slot].items[0] = DropItem {
+                item: slot as u32,
+                amount: slot as u32 + 1,
+            };
+        }
+
+        npc
+    }
+
+    #[test]
+    fn chances_include_free_shares() {
+        let npc = npc_with(&[1, 0, 3], 4);
+
+        assert_eq!(npc.total_shares(), 8);
+        assert_eq!(
+            npc.drop_chances(),
+            vec![
+                DropChance {
+                    slot: 0,
+                    shares: 1,
+                    chance: 0.125,
+                },
+                DropChance {
+                    slot: 2,
+                    shares: 3,
+                    chance: 0.375,
+                },
+            ]
+        );
+        assert_eq!(npc.nothing_chance(), 0.5);
+        assert_eq!(
+            npc.expected_drops(),
+            BTreeMap::from([(0, 0.125), (2, 1.125)])
+        );
+    }
+
+    #[test]
+    fn no_shares_drop_nothing() {
+        let npc = npc_with(&[0, 0], 0);
+
+        assert_eq!(npc.total_shares(), 0);
+        assert!(npc.drop_chances().is_empty());
+        assert_eq!(npc.nothing_chance(), 1.0);
+        assert!(npc.expected_drops().is_empty());
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn roll_drop_follows_the_shares() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let npc = npc_with(&[1, 0, 3], 4);
+        let mut rolls = [0u64; 4];
+
+        for _ in 0..40_000 {
+            match npc.roll_drop(&mut rng) {
+                Some(slot) => rolls[slot] += 1,
+                None => rolls[3] += 1,
+            }
+        }
+
+        assert_eq!(rolls[1], 0);
+
+        for (count, chance) in [(rolls[0], 0.125), (rolls[2], 0.375), (rolls[3], 0.5)] {
+            assert!(
+                (count as f64 / 40_000.0 - chance).abs() < 0.02,
+                "{:?}",
+                rolls
+            );
+        }
+
+        assert_eq!(npc_with(&[], 0).roll_drop(&mut rng), None);
+        assert_eq!(npc_with(&[0, 2], 0).roll_drop(&mut rng), Some(1));
+    }
+
+    #[cfg(feature = "simulation")]
+    #[test]
+    fn simulation_counts_every_kill() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let npc = npc_with(&[1, 0, 3], 4);
+        let simulation = DropSimulation::run(&npc, 1000, &mut rng);
+
+        assert_eq!(simulation.kills, 1000);
+        assert_eq!(simulation.slots[1], 0);
+        assert_eq!(
+            simulation.nothing() + simulation.slots[0] + simulation.slots[2],
+            1000
+        );
+        assert_eq!(simulation.items.get(&0), Some(&simulation.slots[0]));
+        assert_eq!(simulation.items.get(&2), Some(&(simulation.slots[2] * 3)));
+        assert_eq!(simulation.per_kill(500), 0.5);
+        assert_eq!(DropSimulation::default().per_kill(500), 0.0);
+    }
+
+    #[test]
+    fn drop_value_uses_the_base_price() {
+        let items = vec![
+            ItemData {
+                baseprice: 10,
+                ..Default::default()
+            },
+            ItemData {
+                baseprice: 4,
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(drop_value([(0, 0.5), (1, 2.0), (9, 100.0)], &items), 13.0);
+        assert_eq!(drop_value([], &items), 0.0);
+    }
+}
@@ -0,0 +1,69 @@
+use crate::*;
+use serde_json::Value;
+
+/// Turns a dotted field path such as `rgba.r` or `drops.0.shares` into a
+/// json pointer, numbers indexing into arrays. An empty path is the record.
+fn field_pointer(path: &str) -> String {
+    if path.is_empty() {
+        return String::new();
+    }
+
+    path.split('.')
+        .map(|part| format!("/{}", part.replace('~', "~0").replace('/', "~1")))
+        .collect()
+}
+
+/// Looks up a field of a record serialized to json by its dotted path.
+pub fn get_field<'a>(record: &'a Value, path: &str) -> Option<&'a Value> {
+    record.pointer(&field_pointer(path))
+}
+
+/// Returns a copy of `record` with the field at `path` set to `value`. The
+/// value has to fit the type of the field or an error is returned.
+pub fn set_field<T: GameData>(record: &T, path: &str, value: Value) -> Result<T, String> {
+    let mut json = serde_json::to_value(record).map_err(|e| format!("Serdes Error {:?}", e))?;
+
+    match json.pointer_mut(&field_pointer(path)) {
+        Some(field) => *field = value,
+        None => return Err(format!("{} has no field {}", T::NAME, path)),
+    }
+
+    serde_json::from_value(json).map_err(|e| format!("Invalid value for {}, Err {}", path, e))
+}
+
+/// Dotted paths of every field that differs between two records serialized
+/// to json, along with both values. Missing fields are compared as null.
+pub fn diff_fields(left: &Value, right: &Value) -> Vec<(String, Value, Value)> {
+    let mut differences = Vec::new();
+    diff_into(String::new(), left, right, &mut differences);
+    differences
+}
+
+fn diff_into(path: String, left: &Value, right: &Value, out: &mut Vec<(String, Value, Value)>) {
+    let join = |key: &dyn std::fmt::Display| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+
+    match (left, right) {
+        (Value::Object(l), Value::Object(r)) => {
+            for (key, value) in l {
+                diff_into(join(key), value, r.get(key).unwrap_or(&Value::Null), out);
+            }
+
+            for (key, value) in r.iter().filter(|(key, _)| !l.contains_key(*key)) {
+                diff_into(join(key), &Value::Null, value, out);
+            }
+        }
+        (Value::Array(l), Value::Array(r)) if l.len() == r.len() => {
+            for (i, (l, r)) in l.iter().zip(r).enumerate() {
+                diff_into(join(&i), l, r, out);
+            }
+        }
+        _ if left != right => out.push((path, left.clone(), right.clone())),
+        _ => {}
+    }
+}
@@ -0,0 +1,126 @@
+use chrono::{Local, NaiveDateTime};
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Time stamp format used for backup file names.
+pub const BACKUP_TIME_FORMAT: &str = "%Y%m%d_%H%M%S%.3f";
+
+/// Writes into `{name}.tmp` first and renames it over `name` once the data
+/// reached the disk, so a crash mid save never leaves a half written file.
+pub fn write_atomic(name: &Path, bytes: &[u8]) -> Result<(), String> {
+    let mut temp = name.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+
+    match OpenOptions::new()
+        .truncate(true)
+        .write(true)
+        .create(true)
+        .open(&temp)
+    {
+        Ok(mut file) => {
+            if let Err(e) = file.write_all(bytes) {
+                return Err(format!("File Error {:?}", e));
+            }
+
+            if let Err(e) = file.sync_all() {
+                return Err(format!("File Error {:?}", e));
+            }
+        }
+        Err(e) => return Err(format!("Failed to open {}, Err {:?}", temp.display(), e)),
+    }
+
+    fs::rename(&temp, name).map_err(|e| {
+        format!(
+            "Failed to move {} to {}, Err {:?}",
+            temp.display(),
+            name.display(),
+            e
+        )
+    })
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub time: NaiveDateTime,
+}
+
+impl Backup {
+    pub fn label(&self) -> String {
+        self.time.format("%Y-%m-%d %H:%M:%S").to_string()
+    }
+}
+
+/// Copies `name` into `folder` under the current time and removes all but
+/// the newest `keep` backups of that folder.
+pub fn backup_file(name: &Path, folder: &Path, keep: usize) -> Result<(), String> {
+    if keep == 0 || !name.exists() {
+        return Ok(());
+    }
+
+    if let Err(e) = fs::create_dir_all(folder) {
+        return Err(format!(
+            "Failed to create {}, Err {:?}",
+            folder.display(),
+            e
+        ));
+    }
+
+    let target = folder.join(format!(
+        "{}.bin",
+        Local::now().naive_local().format(BACKUP_TIME_FORMAT)
+    ));
+
+    if let Err(e) = fs::copy(name, &target) {
+        return Err(format!(
+            "Failed to copy {} to {}, Err {:?}",
+            name.display(),
+            target.display(),
+            e
+        ));
+    }
+
+    for old in list_backups(folder).iter().skip(keep) {
+        if let Err(e) = fs::remove_file(&old.path) {
+            return Err(format!(
+                "Failed to remove {}, Err {:?}",
+                old.path.display(),
+                e
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Backups within `folder`, newest first.
+pub fn list_backups(folder: &Path) -> Vec<Backup> {
+    let mut backups = Vec::new();
+
+    let Ok(entries) = fs::read_dir(folder) else {
+        return backups;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+
+        if path.extension().and_then(|ext| ext.to_str()) != Some("bin") {
+            continue;
+        }
+
+        let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) else {
+            continue;
+        };
+
+        if let Ok(time) = NaiveDateTime::parse_from_str(stem, BACKUP_TIME_FORMAT) {
+            backups.push(Backup { path, time });
+        }
+    }
+
+    backups.sort_by_key(|backup| std::cmp::Reverse(backup.time));
+    backups
+}
@@ -0,0 +1,349 @@
+use crate::*;
+use chrono::Local;
+use std::io::Read;
+use std::{
+    fs::{self, OpenOptions},
+    path::{Path, PathBuf},
+};
+
+/// File storage shared by every editor record type.
+///
+/// Each record is kept as `{root}/FOLDER/{id}.bin` with a pretty printed copy
+/// in `{root}/FOLDER/json/{id}.json`, where root is the data root of the open
+/// project. Loaded records are paired with a dirty flag which is set by the
+/// editors on change and cleared again once the record is saved. Whether the
+/// bin file or the json copy is read first is chosen by the `DataSource` of
+/// the editor. Every `Record` has it.
+pub trait GameData: Record {
+    fn folder(root: &Path) -> PathBuf {
+        root.join(Self::FOLDER)
+    }
+
+    fn json_path(root: &Path, id: usize) -> PathBuf {
+        Self::folder(root).join("json").join(format!("{}.json", id))
+    }
+
+    fn bin_path(root: &Path, id: usize) -> PathBuf {
+        Self::folder(root).join(format!("{}.bin", id))
+    }
+
+    /// Where a broken bin file is moved to. The time stamp keeps repeated
+    /// failures of the same record from overwriting each other.
+    fn quarantine_path(root: &Path, id: usize) -> PathBuf {
+        Self::folder(root).join("quarantine").join(format!(
+            "{}_{}.bin",
+            id,
+            Local::now().format("%Y%m%d_%H%M%S")
+        ))
+    }
+
+    fn backup_folder(root: &Path, id: usize) -> PathBuf {
+        Self::folder(root).join("backups").join(id.to_string())
+    }
+
+    /// Previous versions of a record, newest first.
+    fn backups(root: &Path, id: usize) -> Vec<Backup> {
+        list_backups(&Self::backup_folder(root, id))
+    }
+
+    fn load_backup(backup: &Backup) -> Result<Self, String> {
+        match fs::read(&backup.path) {
+            Ok(bytes) => Self::from_bin(&bytes)
+                .map(|(data, _)| data)
+                .map_err(|e| format!("Failed to read {}, Err {}", backup.path.display(), e)),
+            Err(e) => Err(format!(
+                "Failed to open {}, Err {:?}",
+                backup.path.display(),
+                e
+            )),
+        }
+    }
+
+    fn create_folders(root: &Path) -> Result<(), String> {
+        let name = Self::folder(root).join("json");
+
+        fs::create_dir_all(&name)
+            .map_err(|e| format!("Failed to create {}, Err {:?}", name.display(), e))
+    }
+
+    /// Amount of records stored within the data root. Records are numbered
+    /// from 0 without gaps, so this counts up to the first id that has
+    /// neither a bin file nor a json copy.
+    fn count(root: &Path) -> usize {
+        let mut count = 0;
+
+        while Self::bin_path(root, count).exists() || Self::json_path(root, count).exists() {
+            count += 1;
+        }
+
+        count
+    }
+
+    /// Removes the files of a record. The bin file is moved into the backup
+    /// folder first so the record can still be restored by hand.
+    fn delete_files(root: &Path, id: usize) -> Result<(), String> {
+        let name = Self::bin_path(root, id);
+
+        backup_file(&name, &Self::backup_folder(root, id), Self::BACKUPS)?;
+
+        for name in [name, Self::json_path(root, id)] {
+            match fs::remove_file(&name) {
+                Ok(()) => {}
+                Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(format!("Failed to remove {}, Err {:?}", name.display(), e)),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn save_file(&self, root: &Path, id: usize) -> Result<(), String> {
+        match serde_json::to_vec_pretty(self) {
+            Ok(bytes) => write_atomic(&Self::json_path(root, id), &bytes),
+            Err(e) => Err(format!("Serdes File Error {:?}", e)),
+        }
+    }
+
+    /// Writes the bin file, moving the version it replaces into the
+    /// record's backup folder.
+    fn save_bin_file(&self, root: &Path, id: usize) -> Result<(), String> {
+        let name = Self::bin_path(root, id);
+        let bytes = self.to_bin()?;
+
+        backup_file(&name, &Self::backup_folder(root, id), Self::BACKUPS)?;
+        write_atomic(&name, &bytes)
+    }
+
+    /// Saves the bin file and, if `save_json` is set, the json copy.
+    fn save(&self, root: &Path, id: usize, save_json: bool) -> Result<(), String> {
+        if save_json {
+            self.save_file(root, id)?;
+        }
+
+        self.save_bin_file(root, id)
+    }
+
+    /// Saves every dirty record, clearing the flag of those that succeeded.
+    /// Returns the errors of the records that could not be saved.
+    fn save_all(root: &Path, data: &mut [(Self, bool)], save_json: bool) -> Vec<String> {
+        let mut errors = Vec::new();
+
+        for (i, v) in data.iter_mut().enumerate() {
+            if !v.1 {
+                continue;
+            }
+
+            match v.0.save(root, i, save_json) {
+                Ok(()) => v.1 = false,
+                Err(e) => errors.push(format!("Could not save {} {}, err {}", Self::NAME, i, e)),
+            }
+        }
+
+        errors
+    }
+
+    /// Loads every record from the given source. Records whose bin file
+    /// can not be read are moved into quarantine and recovered from their json
+    /// copy, or reset to default if that fails too. Those are listed in the
+    /// returned report, along with records whose bin and json disagree when
+    /// json copies are kept. A data root without any records gets a default
+    /// record 0.
+    fn load_files(
+        root: &Path,
+        source: DataSource,
+        save_json: bool,
+    ) -> Result<(Vec<(Self, bool)>, LoadReport), String> {
+        let save_json = save_json || source == DataSource::Json;
+        let mut count = Self::count(root);
+        let mut data = Vec::<(Self, bool)>::with_capacity(count.max(1));
+        let mut report = LoadReport::default();
+
+        if count == 0 {
+            Self::default().save(root, 0, save_json)?;
+            count = 1;
+        }
+
+        for i in 0..count {
+            let bin = Self::load_file(root, i);
+            let json = if save_json && Self::json_path(root, i).exists() {
+                Some(Self::load_json_file(root, i))
+            } else {
+                None
+            };
+
+            if let (Ok((bin, _)), Some(Ok(json))) = (&bin, &json) {
+                report.conflicts.extend(Self::find_conflict(i, bin, json));
+            }
+
+            let mut result = match (source, json) {
+                (DataSource::Json, Some(Ok(json))) => (json, bin.is_err()),
+                (_, json) => {
+                    let mut result = match bin {
+                        Ok(result) => result,
+                        Err(error) => {
+                            let quarantined = Self::quarantine(root, i)?;
+                            let (record, recovered) = Self::recover(root, i);
+
+                            record.save_bin_file(root, i)?;
+                            report.issues.push(LoadIssue {
+                                name: Self::NAME,
+                                id: i,
+                                error,
+                                quarantined,
+                                source: recovered,
+                            });
+                            (record, false)
+                        }
+                    };
+
+                    match json {
+                        // Left alone so the hand edit can still be fixed.
+                        Some(Err(error)) if source == DataSource::Json => {
+                            report.issues.push(LoadIssue {
+                                name: Self::NAME,
+                                id: i,
+                                error,
+                                quarantined: None,
+                                source: RecoverySource::Bin,
+                            });
+                        }
+                        None if source == DataSource::Json => result.1 = true,
+                        _ => {}
+                    }
+
+                    result
+                }
+            };
+
+            if result.1 {
+                result.0.save(root, i, save_json)?;
+                result.1 = false;
+            }
+
+            data.push(result);
+        }
+        Ok((data, report))
+    }
+
+    /// Compares both versions of a record, returning the fields they
+    /// disagree on or None if they match.
+    fn find_conflict(id: usize, bin: &Self, json: &Self) -> Option<Conflict> {
+        let bin = serde_json::to_value(bin).ok()?;
+        let json = serde_json::to_value(json).ok()?;
+
+        let differences: Vec<_> = diff_fields(&bin, &json)
+            .into_iter()
+            .map(|(field, bin, json)| FieldDifference { field, bin, json })
+            .collect();
+
+        if differences.is_empty() {
+            return None;
+        }
+
+        Some(Conflict {
+            name: Self::NAME,
+            id,
+            differences,
+        })
+    }
+
+    /// Settles a conflict by rewriting the losing file from the chosen one,
+    /// returning the record that won.
+    fn resolve_conflict(root: &Path, id: usize, keep: DataSource) -> Result<Self, String> {
+        match keep {
+            DataSource::Bin => {
+                let (data, _) = Self::load_file(root, id)?;
+                data.save_file(root, id)?;
+                Ok(data)
+            }
+            DataSource::Json => {
+                let data = Self::load_json_file(root, id)?;
+                data.save_bin_file(root, id)?;
+                Ok(data)
+            }
+        }
+    }
+
+    /// Moves the bin file of a record into the quarantine folder, returning
+    /// its new path or None if there was no file to move.
+    fn quarantine(root: &Path, id: usize) -> Result<Option<String>, String> {
+        let name = Self::bin_path(root, id);
+
+        if !name.exists() {
+            return Ok(None);
+        }
+
+        let target = Self::quarantine_path(root, id);
+
+        if let Some(folder) = target.parent() {
+            if let Err(e) = fs::create_dir_all(folder) {
+                return Err(format!(
+                    "Failed to create {}, Err {:?}",
+                    folder.display(),
+                    e
+                ));
+            }
+        }
+
+        match fs::rename(&name, &target) {
+            Ok(()) => Ok(Some(target.display().to_string())),
+            Err(e) => Err(format!(
+                "Failed to move {} to {}, Err {:?}",
+                name.display(),
+                target.display(),
+                e
+            )),
+        }
+    }
+
+    /// Rebuilds a record from its json copy, falling back to default.
+    fn recover(root: &Path, id: usize) -> (Self, RecoverySource) {
+        match Self::load_json_file(root, id) {
+            Ok(data) => (data, RecoverySource::Json),
+            Err(_) => (Self::default(), RecoverySource::Default),
+        }
+    }
+
+    /// Reads the json copy of a record.
+    fn load_json_file(root: &Path, id: usize) -> Result<Self, String> {
+        let name = Self::json_path(root, id);
+
+        match fs::read(&name) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Failed to read {}, Err {}", name.display(), e)),
+            Err(e) => Err(format!("Failed to open {}, Err {:?}", name.display(), e)),
+        }
+    }
+
+    /// Loads a single record from the given source, falling back to the bin
+    /// file if a json source has no copy of it.
+    fn load_record(root: &Path, id: usize, source: DataSource) -> Result<Self, String> {
+        match source {
+            DataSource::Json if Self::json_path(root, id).exists() => {
+                Self::load_json_file(root, id)
+            }
+            _ => Self::load_file(root, id).map(|(data, _)| data),
+        }
+    }
+
+    /// Loads a record, flagging it dirty when it was upgraded so `load_files`
+    /// rewrites it in the current layout. Files that can not be read are an
+    /// error, so they go through the same quarantine as broken ones.
+    fn load_file(root: &Path, id: usize) -> Result<(Self, bool), String> {
+        let name = Self::bin_path(root, id);
+
+        match OpenOptions::new().read(true).open(&name) {
+            Ok(mut file) => {
+                let mut bytes = Vec::new();
+                match file.read_to_end(&mut bytes) {
+                    Ok(_) => Self::from_bin(&bytes)
+                        .map_err(|e| format!("Failed to read {}, Err {}", name.display(), e)),
+                    Err(e) => Err(format!("Failed to read {}, Err {:?}", name.display(), e)),
+                }
+            }
+            Err(e) => Err(format!("Failed to open {}, Err {:?}", name.display(), e)),
+        }
+    }
+}
+
+impl<T: Record> GameData for T {}
@@ -0,0 +1,234 @@
+use crate::*;
+use ascending_types::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// What one index of `ItemData.data` holds for an item type.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataField {
+    pub index: usize,
+    /// Name of the value for tools and the game, such as `melee_damage`.
+    #[serde(default)]
+    pub meaning: String,
+    /// Name shown within the editor.
+    pub label: String,
+    #[serde(default = "DataField::default_min")]
+    pub min: i16,
+    #[serde(default = "DataField::default_max")]
+    pub max: i16,
+    #[serde(default)]
+    pub tooltip: String,
+}
+
+impl DataField {
+    fn default_min() -> i16 {
+        i16::MIN
+    }
+
+    fn default_max() -> i16 {
+        i16::MAX
+    }
+}
+
+impl From<&PayloadField> for DataField {
+    fn from(field: &PayloadField) -> Self {
+        Self {
+            index: field.index,
+            meaning: field.meaning.into(),
+            label: field.label.into(),
+            min: field.min,
+            max: field.max,
+            tooltip: field.tooltip.into(),
+        }
+    }
+}
+
+/// Data fields used by one item type.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemTypeSchema {
+    pub item_type: ItemTypes,
+    pub fields: Vec<DataField>,
+}
+
+/// Meaning of `ItemData.data` per item type, stored as
+/// `{data}/item_schema.json` so new game mechanics only need the file
+/// changed. Projects without one use the built in schema.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemSchema {
+    pub types: Vec<ItemTypeSchema>,
+}
+
+/// The built in schema, made from the typed payloads of `ItemPayload`.
+impl Default for ItemSchema {
+    fn default() -> Self {
+        let types = (0..ItemTypes::Count as usize)
+            .map(ItemTypes::from_index)
+            .filter(|item_type| !ItemPayload::fields(*item_type).is_empty())
+            .map(|item_type| ItemTypeSchema {
+                item_type,
+                fields: ItemPayload::fields(item_type)
+                    .iter()
+                    .map(DataField::from)
+                    .collect(),
+            })
+            .collect();
+
+        Self { types }
+    }
+}
+
+impl ItemSchema {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join("item_schema.json")
+    }
+
+    /// Loads the item schema of a data root, using the built in one if the
+    /// root has none.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let name = Self::path(root);
+
+        if !name.exists() {
+            return Ok(Self::default());
+        }
+
+        let schema: Self = match fs::read(&name) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Failed to read {}, Err {:?}", name.display(), e))?,
+            Err(e) => return Err(format!("Failed to open {}, Err {:?}", name.display(), e)),
+        };
+
+        schema
+            .check()
+            .map_err(|e| format!("Invalid {}, {}", name.display(), e))?;
+        Ok(schema)
+    }
+
+    pub fn save(&self, root: &Path) -> Result<(), String> {
+        match serde_json::to_vec_pretty(self) {
+            Ok(bytes) => write_atomic(&Self::path(root), &bytes),
+            Err(e) => Err(format!("Serdes File Error {:?}", e)),
+        }
+    }
+
+    /// Makes sure every field fits within `ItemData.data`, has a usable
+    /// range and is only listed once per item type.
+    pub fn check(&self) -> Result<(), String> {
+        for (i, schema) in self.types.iter().enumerate() {
+            if self.types[..i]
+                .iter()
+                .any(|other| other.item_type == schema.item_type)
+            {
+                return Err(format!("{} is listed twice", schema.item_type));
+            }
+
+            for (j, field) in schema.fields.iter().enumerate() {
+                if field.index >= ITEM_DATA_LEN {
+                    return Err(format!(
+                        "{} data {} is past the last index {}",
+                        schema.item_type,
+                        field.index,
+                        ITEM_DATA_LEN - 1
+                    ));
+                }
+
+                if field.min > field.max {
+                    return Err(format!(
+                        "{} data {} has a min above its max",
+                        schema.item_type, field.index
+                    ));
+                }
+
+                if schema.fields[..j]
+                    .iter()
+                    .any(|other| other.index == field.index)
+                {
+                    return Err(format!(
+                        "{} data {} is listed twice",
+                        schema.item_type, field.index
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Data fields of an item type, ordered by index. Types the schema does
+    /// not list have none.
+    pub fn fields_of(&self, item_type: ItemTypes) -> Vec<&DataField> {
+        let mut fields: Vec<&DataField> = self
+            .types
+            .iter()
+            .filter(|schema| schema.item_type == item_type)
+            .flat_map(|schema| &schema.fields)
+            .collect();
+
+        fields.sort_by_key(|field| field.index);
+        fields
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_schema_matches_the_payloads() {
+        let schema = ItemSchema::default();
+
+        assert_eq!(schema.check(), Ok(()));
+
+        for item_type in (0..ItemTypes::Count as usize).map(ItemTypes::from_index) {
+            let fields: Vec<(usize, &str)> = schema
+                .fields_of(item_type)
+                .into_iter()
+                .map(|field| (field.index, field.meaning.as_str()))
+                .collect();
+            let payload: Vec<(usize, &str)> = ItemPayload::fields(item_type)
+                .iter()
+                .map(|field| (field.index, field.meaning))
+                .collect();
+
+            assert_eq!(fields, payload, "{:?}", item_type);
+        }
+    }
+
+    #[test]
+    fn item_payload_round_trips() {
+        for item_type in (0..ItemTypes::Count as usize).map(ItemTypes::from_index) {
+            let mut item = ItemData {
+                itemtype: item_type,
+                data: std::array::from_fn(|i| i as i16 * 3 - 7),
+                ..Default::default()
+            };
+            let before = item.clone();
+
+            item.set_payload(&item.payload()).unwrap();
+
+            assert_eq!(item, before, "{:?}", item_type);
+        }
+    }
+
+    #[test]
+    fn item_payload_of_another_type_is_refused() {
+        let mut item = ItemData {
+            itemtype: ItemTypes::Weapon,
+            ..Default::default()
+        };
+        let armor = ItemData {
+            itemtype: ItemTypes::Helmet,
+            data: [4; ITEM_DATA_LEN],
+            ..Default::default()
+        };
+
+        assert!(item.set_payload(&armor.payload()).is_err());
+        assert_eq!(item.data, [0; ITEM_DATA_LEN]);
+
+        item.itemtype = ItemTypes::Boots;
+        assert!(item.set_payload(&armor.payload()).is_ok());
+        assert_eq!(item.data, armor.data);
+    }
+}
@@ -0,0 +1,61 @@
+use crate::*;
+use ascending_types::*;
+use serde::{Deserialize, Serialize};
+use speedy::{Readable, Writable};
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Educe, Readable, Writable)]
+#[educe(Default)]
+pub struct ItemData {
+    pub name: String,
+    pub levelreq: u16,
+    pub soundid: u16,
+    pub sprite: u16,
+    pub animation: Option<u32>,
+    pub data: [i16; ITEM_DATA_LEN],
+    pub itemtype: ItemTypes,
+    pub itemtype2: u8,
+    pub breakable: bool,
+    pub stackable: bool,
+    #[educe(Default = 1)]
+    pub stacklimit: u16,
+    pub baseprice: u64,
+    pub repairable: bool,
+    pub rgba: Rgba,
+    pub sound_index: Option<String>,
+}
+
+impl Record for ItemData {
+    const FOLDER: &'static str = "items";
+    const MAX: usize = MAX_ITEMS;
+    const NAME: &'static str = "Item";
+    const VERSION: u16 = 1;
+
+    fn migrations() -> Migrations<Self> {
+        // Version 0 bins have no header but share the version 1 layout.
+        Migrations::new().register(0, read_unchanged)
+    }
+}
+
+impl ItemData {
+    /// Typed view of `data` by the item type, for game code and tools. The
+    /// editor form is driven by the `ItemSchema` of the project instead, as
+    /// a project can relabel or add fields; its built in schema is made from
+    /// these same payloads.
+    pub fn payload(&self) -> TypedItemData {
+        TypedItemData::new(self.itemtype, &self.data)
+    }
+
+    /// Replaces `data` with a typed payload, which has to be the payload of
+    /// the item type.
+    pub fn set_payload(&mut self, payload: &TypedItemData) -> Result<(), String> {
+        if !payload.payload.fits(self.itemtype) {
+            return Err(format!(
+                "The payload does not belong to item type {:?}",
+                self.itemtype
+            ));
+        }
+
+        self.data = payload.to_data();
+        Ok(())
+    }
+}
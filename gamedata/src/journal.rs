@@ -0,0 +1,252 @@
+use crate::*;
+use chrono::{Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// How often the editors write their recovery journal, in seconds.
+pub const AUTOSAVE_SECS: u64 = 30;
+
+/// Unsaved records as written into `{root}/FOLDER/recovery.json`. The
+/// journal of a previous session is kept as `recovery.prev.json` until it is
+/// restored or discarded.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "T: GameData")]
+pub struct JournalFile<T> {
+    pub time: NaiveDateTime,
+    pub records: BTreeMap<usize, T>,
+}
+
+/// Copy of the unsaved records of an editor. It lives outside the editor so
+/// the panic hook can still write it out when the editor goes down.
+#[derive(Debug, Default)]
+pub struct Journal<T> {
+    root: PathBuf,
+    records: BTreeMap<usize, T>,
+    changed: bool,
+}
+
+impl<T: GameData + PartialEq> Journal<T> {
+    pub fn new(root: &Path) -> Self {
+        Self {
+            root: root.to_owned(),
+            records: BTreeMap::new(),
+            changed: false,
+        }
+    }
+
+    pub fn path(root: &Path) -> PathBuf {
+        T::folder(root).join("recovery.json")
+    }
+
+    pub fn previous_path(root: &Path) -> PathBuf {
+        T::folder(root).join("recovery.prev.json")
+    }
+
+    /// Switches to the data root of another project, forgetting the records
+    /// of the previous one.
+    pub fn set_root(&mut self, root: &Path) {
+        self.root = root.to_owned();
+        self.records.clear();
+        self.changed = false;
+    }
+
+    /// Mirrors the dirty records of an editor. Once nothing is unsaved
+    /// anymore the journal file is removed right away.
+    pub fn track(&mut self, data: &[(T, bool)]) -> Result<(), String> {
+        let count = self.records.len();
+
+        self.records
+            .retain(|id, _| data.get(*id).is_some_and(|(_, dirty)| *dirty));
+        self.changed |= count != self.records.len();
+
+        for (id, (record, _)) in data.iter().enumerate().filter(|(_, data)| data.1) {
+            if self.records.get(&id) != Some(record) {
+                self.records.insert(id, record.clone());
+                self.changed = true;
+            }
+        }
+
+        if self.records.is_empty() {
+            self.write()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the journal file if the records changed since the last write.
+    pub fn write(&mut self) -> Result<(), String> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        if self.records.is_empty() {
+            Self::discard(&self.root)?;
+        } else {
+            let file = JournalFile {
+                time: Local::now().naive_local(),
+                records: self.records.clone(),
+            };
+
+            match serde_json::to_vec_pretty(&file) {
+                Ok(bytes) => write_atomic(&Self::path(&self.root), &bytes)?,
+                Err(e) => return Err(format!("Serdes File Error {:?}", e)),
+            }
+        }
+
+        self.changed = false;
+        Ok(())
+    }
+
+    /// Moves the journal left behind by a previous session aside, so this
+    /// session writing its own journal can not touch it, and reads it. A
+    /// journal still aside from an earlier session that never answered is
+    /// kept, with the records of the newer one merged on top.
+    pub fn take_previous(root: &Path) -> Result<Option<JournalFile<T>>, String> {
+        let name = Self::path(root);
+        let previous_name = Self::previous_path(root);
+
+        let Some(current) = Self::read(&name)? else {
+            return Self::read(&previous_name);
+        };
+
+        let file = match Self::read(&previous_name)? {
+            Some(mut previous) => {
+                previous.time = current.time;
+                previous.records.extend(current.records);
+
+                match serde_json::to_vec_pretty(&previous) {
+                    Ok(bytes) => write_atomic(&previous_name, &bytes)?,
+                    Err(e) => return Err(format!("Serdes File Error {:?}", e)),
+                }
+
+                Self::discard(root)?;
+                previous
+            }
+            None => {
+                fs::rename(&name, &previous_name).map_err(|e| {
+                    format!(
+                        "Failed to move {} to {}, Err {:?}",
+                        name.display(),
+                        previous_name.display(),
+                        e
+                    )
+                })?;
+                current
+            }
+        };
+
+        Ok(Some(file))
+    }
+
+    fn read(name: &Path) -> Result<Option<JournalFile<T>>, String> {
+        if !name.exists() {
+            return Ok(None);
+        }
+
+        match fs::read(name) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| format!("Failed to read {}, Err {}", name.display(), e)),
+            Err(e) => Err(format!("Failed to open {}, Err {:?}", name.display(), e)),
+        }
+    }
+
+    pub fn discard(root: &Path) -> Result<(), String> {
+        remove_journal(&Self::path(root))
+    }
+
+    /// Removes the journal of a previous session once it was restored or
+    /// discarded.
+    pub fn discard_previous(root: &Path) -> Result<(), String> {
+        remove_journal(&Self::previous_path(root))
+    }
+}
+
+fn remove_journal(name: &Path) -> Result<(), String> {
+    match fs::remove_file(name) {
+        Ok(()) => Ok(()),
+        Err(ref e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(format!("Failed to remove {}, Err {:?}", name.display(), e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("ascending_journal_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(ItemData::FOLDER)).unwrap();
+        root
+    }
+
+    fn dirty(name: &str) -> (ItemData, bool) {
+        let item = ItemData {
+            name: name.into(),
+            ..Default::default()
+        };
+
+        (item, true)
+    }
+
+    fn names(file: &JournalFile<ItemData>) -> Vec<(usize, &str)> {
+        file.records
+            .iter()
+            .map(|(id, item)| (*id, item.name.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn previous_journal_survives_the_next_session() {
+        let root = root("survives");
+        let mut journal = Journal::<ItemData>::new(&root);
+
+        journal.track(&[dirty("crashed")]).unwrap();
+        journal.write().unwrap();
+
+        let previous = Journal::<ItemData>::take_previous(&root).unwrap().unwrap();
+        assert_eq!(names(&previous), vec![(0, "crashed")]);
+
+        // The next session saving everything removes its own journal only.
+        let mut journal = Journal::<ItemData>::new(&root);
+        journal.track(&[dirty("new")]).unwrap();
+        journal.write().unwrap();
+        journal.track(&[(ItemData::default(), false)]).unwrap();
+
+        assert!(!Journal::<ItemData>::path(&root).exists());
+
+        let previous = Journal::<ItemData>::take_previous(&root).unwrap().unwrap();
+        assert_eq!(names(&previous), vec![(0, "crashed")]);
+
+        Journal::<ItemData>::discard_previous(&root).unwrap();
+        assert!(Journal::<ItemData>::take_previous(&root).unwrap().is_none());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn unanswered_journals_are_merged() {
+        let root = root("merged");
+        let mut journal = Journal::<ItemData>::new(&root);
+
+        journal.track(&[dirty("first"), dirty("kept")]).unwrap();
+        journal.write().unwrap();
+        Journal::<ItemData>::take_previous(&root).unwrap();
+
+        let mut journal = Journal::<ItemData>::new(&root);
+        journal.track(&[dirty("second")]).unwrap();
+        journal.write().unwrap();
+
+        let previous = Journal::<ItemData>::take_previous(&root).unwrap().unwrap();
+        assert_eq!(names(&previous), vec![(0, "second"), (1, "kept")]);
+        assert!(!Journal::<ItemData>::path(&root).exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,52 @@
+use std::fmt;
+
+/// The record types of a project.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RecordKind {
+    Item,
+    Npc,
+    Shop,
+}
+
+impl RecordKind {
+    pub const ALL: [RecordKind; 3] = [RecordKind::Item, RecordKind::Npc, RecordKind::Shop];
+
+    /// Parses a kind by its name or data folder, such as `item` or `items`.
+    pub fn parse(arg: &str) -> Result<Self, String> {
+        match arg {
+            "items" | "item" => Ok(RecordKind::Item),
+            "npcs" | "npc" => Ok(RecordKind::Npc),
+            "shops" | "shop" => Ok(RecordKind::Shop),
+            _ => Err(format!("Unknown kind {}", arg)),
+        }
+    }
+}
+
+impl fmt::Display for RecordKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecordKind::Item => f.write_str("Item"),
+            RecordKind::Npc => f.write_str("Npc"),
+            RecordKind::Shop => f.write_str("Shop"),
+        }
+    }
+}
+
+/// A single record of any kind.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RecordRef {
+    pub kind: RecordKind,
+    pub id: usize,
+}
+
+impl RecordRef {
+    pub fn new(kind: RecordKind, id: usize) -> Self {
+        Self { kind, id }
+    }
+}
+
+impl fmt::Display for RecordRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.kind, self.id)
+    }
+}
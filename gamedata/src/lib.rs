@@ -0,0 +1,72 @@
+#[macro_use]
+extern crate educe;
+
+mod drops;
+mod itemdata;
+mod kind;
+mod npcdata;
+mod record;
+mod shopdata;
+mod version;
+
+#[cfg(feature = "files")]
+mod assets;
+#[cfg(feature = "files")]
+mod bulk;
+#[cfg(feature = "files")]
+mod clipboard;
+#[cfg(feature = "files")]
+mod conflict;
+#[cfg(feature = "files")]
+mod fields;
+#[cfg(feature = "files")]
+mod files;
+#[cfg(feature = "files")]
+mod gamedata;
+#[cfg(feature = "files")]
+mod item_schema;
+#[cfg(feature = "files")]
+mod journal;
+#[cfg(feature = "files")]
+mod project;
+#[cfg(feature = "files")]
+mod references;
+#[cfg(feature = "files")]
+mod renumber;
+#[cfg(feature = "files")]
+mod report;
+
+pub use drops::*;
+pub use itemdata::*;
+pub use kind::*;
+pub use npcdata::*;
+pub use record::*;
+pub use shopdata::*;
+pub use version::*;
+
+#[cfg(feature = "files")]
+pub use assets::*;
+#[cfg(feature = "files")]
+pub use bulk::*;
+#[cfg(feature = "files")]
+pub use clipboard::*;
+#[cfg(feature = "files")]
+pub use conflict::*;
+#[cfg(feature = "files")]
+pub use fields::*;
+#[cfg(feature = "files")]
+pub use files::*;
+#[cfg(feature = "files")]
+pub use gamedata::*;
+#[cfg(feature = "files")]
+pub use item_schema::*;
+#[cfg(feature = "files")]
+pub use journal::*;
+#[cfg(feature = "files")]
+pub use project::*;
+#[cfg(feature = "files")]
+pub use references::*;
+#[cfg(feature = "files")]
+pub use renumber::*;
+#[cfg(feature = "files")]
+pub use report::*;
@@ -0,0 +1,98 @@
+use crate::*;
+use ascending_types::*;
+use serde::{Deserialize, Serialize};
+use speedy::{Readable, Writable};
+
+#[derive(Educe, Clone, Debug, Serialize, Deserialize, Eq, Readable, Writable)]
+#[educe(PartialEq, Default)]
+pub struct DropItem {
+    pub item: u32,
+    pub amount: u32,
+}
+
+#[derive(Educe, Clone, Debug, Serialize, Deserialize, Eq, Readable, Writable)]
+#[educe(PartialEq, Default)]
+pub struct NpcDrop {
+    pub items: [DropItem; 5],
+    pub shares: u32,
+}
+
+#[derive(Educe, Clone, Debug, Serialize, Deserialize, Eq, Readable, Writable)]
+#[educe(PartialEq, Default)]
+pub struct NpcData {
+    pub name: String,
+    #[educe(Default = 1)]
+    pub level: i32,
+    pub sprite: i32,
+    #[educe(Default = 1)]
+    pub respawn_wait: i64,
+    #[educe(Default = 1)]
+    pub movement_wait: i64,
+    #[educe(Default = 1)]
+    pub attack_wait: i64,
+    #[educe(Default = 1)]
+    pub intervaled_wait: i64,
+    #[educe(Default = 1)]
+    pub spawn_wait: i64,
+    #[educe(Default = 1)]
+    pub maxhp: u32,
+    pub maxsp: u32,
+    pub maxmp: u32,
+    pub sight: i32,
+    pub follow_sight: i32,
+    pub walkdistance: u32,
+    #[educe(Default = 1)]
+    pub pdamage: u32,
+    #[educe(Default = 1)]
+    pub pdefense: u32,
+    pub canpassthru: bool,
+    #[educe(Default(expression = TileBox {
+        x: 1,
+        y: 1,
+        width: 1,
+        height: 1,
+    }))]
+    pub size: TileBox,
+    pub behaviour: AIBehavior,
+    #[educe(Default = 1)]
+    pub maxdamage: u32,
+    #[educe(Default = 1)]
+    pub mindamage: u32,
+    pub target_auto_switch: bool,
+    pub target_attacked_switch: bool,
+    pub target_auto_switch_chance: i64,
+    pub target_range_dropout: bool,
+    pub can_target: bool,
+    pub can_move: bool,
+    pub can_attack_player: bool,
+    pub has_allys: bool,
+    pub has_enemies: bool, // New
+    pub can_attack: bool,
+    pub has_selfonly: bool,
+    pub has_friendonly: bool,
+    pub has_groundonly: bool,
+    pub runsaway: bool,
+    pub isanimated: bool,
+    pub run_damage: u32,
+    pub spawntime: (GameTime, GameTime), //skill type to cast it with and  percentage needed to cast and Max Percentage.
+    pub range: i32, // New       //attack range. How far they need to be to hit their target.
+    pub enemies: Vec<u64>, // New //list of enemies the npcs can attack of other npc's... WAR!
+    //pub drops: [(u32, u32, u32); 10], //item dropped on death, chance, amount
+    //pub drops_max: u16,
+    pub drops: [NpcDrop; 10],
+    pub free_shares: u32,
+
+    pub exp: i64,
+}
+
+impl Record for NpcData {
+    const FOLDER: &'static str = "npcs";
+    const MAX: usize = MAX_NPCS;
+    const NAME: &'static str = "Npc";
+    const VERSION: u16 = 1;
+
+    fn migrations() -> Migrations<Self> {
+        // Version 0 bins have no header but share the version 1 layout.
+        Migrations::new().register(0, read_unchanged)
+    }
+}
@@ -0,0 +1,176 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// Amount of projects remembered within the recent project list.
+pub const MAX_RECENT_PROJECTS: usize = 10;
+
+/// Where a game project keeps its data files and sprite resources.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectPaths {
+    pub data: PathBuf,
+    pub resources: PathBuf,
+}
+
+impl Default for ProjectPaths {
+    fn default() -> Self {
+        Self {
+            data: PathBuf::from("./data"),
+            resources: PathBuf::from("./resources"),
+        }
+    }
+}
+
+impl fmt::Display for ProjectPaths {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "data: {}, resources: {}",
+            self.data.display(),
+            self.resources.display()
+        )
+    }
+}
+
+impl ProjectPaths {
+    pub fn new(data: impl Into<PathBuf>, resources: impl Into<PathBuf>) -> Self {
+        Self {
+            data: data.into(),
+            resources: resources.into(),
+        }
+    }
+
+    /// Project using the `data` and `resources` folders of a checkout.
+    pub fn from_root(root: &Path) -> Self {
+        Self::new(root.join("data"), root.join("resources"))
+    }
+
+    /// Applies the `--project <dir>`, `--data <dir>` and `--resources <dir>`
+    /// command line options on top of these paths. `--project` is applied
+    /// first so the other two can override part of it.
+    pub fn with_args<I>(mut self, args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut project = None;
+        let mut data = None;
+        let mut resources = None;
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let target = match arg.as_str() {
+                "--project" => &mut project,
+                "--data" => &mut data,
+                "--resources" => &mut resources,
+                _ => continue,
+            };
+
+            match args.next() {
+                Some(value) => *target = Some(PathBuf::from(value)),
+                None => return Err(format!("Missing folder after {}", arg)),
+            }
+        }
+
+        if let Some(root) = project {
+            self = Self::from_root(&root);
+        }
+
+        if let Some(data) = data {
+            self.data = data;
+        }
+
+        if let Some(resources) = resources {
+            self.resources = resources;
+        }
+
+        Ok(self)
+    }
+
+    /// Fails unless both folders exist, so a mistyped path is not taken for
+    /// a new project.
+    pub fn check_exists(&self) -> Result<(), String> {
+        for (name, path) in [("Data", &self.data), ("Resources", &self.resources)] {
+            if !path.is_dir() {
+                return Err(format!("{} folder {} does not exist", name, path.display()));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check made when an editor starts. Only the default folders may be
+    /// missing, as those are created for a new project on the first launch.
+    pub fn check_startup(&self) -> Result<(), String> {
+        if *self == Self::default() {
+            return Ok(());
+        }
+
+        self.check_exists()
+    }
+
+    /// Moves these paths to the front of a recent project list.
+    pub fn remember(&self, recent: &mut Vec<ProjectPaths>) {
+        recent.retain(|paths| paths != self);
+        recent.insert(0, self.clone());
+        recent.truncate(MAX_RECENT_PROJECTS);
+    }
+}
+
+/// Settings shared by every editor working on a data root, stored as
+/// `{data}/project.json`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProjectConfig {
+    /// Highest amount of records per data folder, such as `"items": 5000`.
+    pub limits: BTreeMap<String, usize>,
+    /// Width and height of the images per sprite folder, such as
+    /// `"items": [20, 20]`. Folders without one expect the size most of
+    /// their images share.
+    pub sprite_sizes: BTreeMap<String, (u32, u32)>,
+}
+
+impl ProjectConfig {
+    pub fn path(root: &Path) -> PathBuf {
+        root.join("project.json")
+    }
+
+    /// Loads the project config of a data root, using the defaults if the
+    /// root has none yet.
+    pub fn load(root: &Path) -> Result<Self, String> {
+        let name = Self::path(root);
+
+        if !name.exists() {
+            return Ok(Self::default());
+        }
+
+        match fs::read(&name) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Failed to read {}, Err {:?}", name.display(), e)),
+            Err(e) => Err(format!("Failed to open {}, Err {:?}", name.display(), e)),
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> Result<(), String> {
+        match serde_json::to_vec_pretty(self) {
+            Ok(bytes) => write_atomic(&Self::path(root), &bytes),
+            Err(e) => Err(format!("Serdes File Error {:?}", e)),
+        }
+    }
+
+    /// Record limit of a data folder, or `default` if none is set.
+    pub fn limit_of(&self, folder: &str, default: usize) -> usize {
+        self.limits.get(folder).copied().unwrap_or(default)
+    }
+
+    pub fn limit<T: GameData>(&self) -> usize {
+        self.limit_of(T::FOLDER, T::MAX)
+    }
+
+    pub fn sprite_size(&self, folder: &str) -> Option<(u32, u32)> {
+        self.sprite_sizes.get(folder).copied()
+    }
+}
@@ -0,0 +1,158 @@
+use crate::*;
+use serde::{de::DeserializeOwned, Serialize};
+use speedy::{LittleEndian, Readable, Writable};
+
+/// A record type the editors write and the game reads, along with its bin
+/// layout. Bin files start with a versioned header so older layouts can be
+/// upgraded through `migrations` instead of failing to load.
+///
+/// This is all a server needs to read the records, the file handling of the
+/// editors is found in `GameData` behind the `files` feature.
+pub trait Record:
+    Clone
+    + Default
+    + Serialize
+    + DeserializeOwned
+    + Writable<LittleEndian>
+    + for<'a> Readable<'a, LittleEndian>
+{
+    /// Folder within the data root the records are stored in.
+    const FOLDER: &'static str;
+    /// Default limit of records of this type, used when the project config
+    /// does not set one.
+    const MAX: usize;
+    /// Record name used within error messages.
+    const NAME: &'static str;
+    /// Layout version written into the bin header.
+    const VERSION: u16;
+    /// Amount of previous bin versions kept per record.
+    const BACKUPS: usize = 5;
+
+    /// Older layouts this record can still be read from.
+    fn migrations() -> Migrations<Self> {
+        Migrations::new()
+    }
+
+    fn to_bin(&self) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::new();
+        write_header(Self::VERSION, &mut bytes);
+
+        match self.write_to_vec() {
+            Ok(data) => bytes.extend_from_slice(&data),
+            Err(e) => return Err(format!("Speedy Write Error {:?}", e)),
+        }
+
+        Ok(bytes)
+    }
+
+    /// Reads a bin file, returning true when it had to be upgraded from an
+    /// older layout.
+    fn from_bin(bytes: &[u8]) -> Result<(Self, bool), String> {
+        let (version, data) = split_header(bytes);
+
+        if version == Self::VERSION {
+            return Ok((read_unchanged(data)?, false));
+        }
+
+        if version > Self::VERSION {
+            return Err(format!(
+                "{} layout version {} is newer than the supported version {}",
+                Self::NAME,
+                version,
+                Self::VERSION
+            ));
+        }
+
+        match Self::migrations().get(version) {
+            Some(migration) => Ok((migration(data)?, true)),
+            None => Err(format!(
+                "No migration registered for {} layout version {}",
+                Self::NAME,
+                version
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    fn item() -> ItemData {
+        ItemData {
+            name: "Sword".into(),
+            levelreq: 12,
+            baseprice: 300,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn split_header_reads_the_version() {
+        let mut bytes = Vec::new();
+        write_header(7, &mut bytes);
+        bytes.extend_from_slice(&[1, 2, 3]);
+
+        assert_eq!(split_header(&bytes), (7, &[1u8, 2, 3][..]));
+    }
+
+    #[test]
+    fn split_header_treats_headerless_bytes_as_version_0() {
+        assert_eq!(split_header(&[1, 2, 3]), (0, &[1u8, 2, 3][..]));
+        assert_eq!(split_header(b"ASDB"), (0, &b"ASDB"[..]));
+    }
+
+    #[test]
+    fn current_version_round_trips_unchanged() {
+        let bytes = item().to_bin().unwrap();
+
+        assert_eq!(split_header(&bytes).0, ItemData::VERSION);
+        assert_eq!(ItemData::from_bin(&bytes).unwrap(), (item(), false));
+    }
+
+    #[test]
+    fn version_0_is_upgraded() {
+        let bytes = item().write_to_vec().unwrap();
+
+        assert_eq!(ItemData::from_bin(&bytes).unwrap(), (item(), true));
+        assert_eq!(
+            NpcData::from_bin(&NpcData::default().write_to_vec().unwrap()).unwrap(),
+            (NpcData::default(), true)
+        );
+        assert_eq!(
+            ShopData::from_bin(&ShopData::default().write_to_vec().unwrap()).unwrap(),
+            (ShopData::default(), true)
+        );
+    }
+
+    #[test]
+    fn newer_version_is_an_error() {
+        let mut bytes = Vec::new();
+        write_header(ItemData::VERSION + 1, &mut bytes);
+        bytes.extend_from_slice(&item().write_to_vec().unwrap());
+
+        assert!(ItemData::from_bin(&bytes).is_err());
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, Readable, Writable)]
+    struct Plain {
+        value: u32,
+    }
+
+    impl Record for Plain {
+        const FOLDER: &'static str = "plain";
+        const MAX: usize = 1;
+        const NAME: &'static str = "Plain";
+        const VERSION: u16 = 2;
+    }
+
+    #[test]
+    fn missing_migration_is_an_error() {
+        let mut bytes = Vec::new();
+        write_header(1, &mut bytes);
+        bytes.extend_from_slice(&Plain { value: 4 }.write_to_vec().unwrap());
+
+        assert!(Plain::from_bin(&bytes).is_err());
+    }
+}
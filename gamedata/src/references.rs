@@ -0,0 +1,266 @@
+use crate::*;
+use std::{collections::HashMap, fmt, path::Path};
+
+/// A field of one record pointing at another record.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reference {
+    pub owner: RecordRef,
+    /// Dotted path of the referencing field, as used by `set_field`.
+    pub field: String,
+    pub target: RecordRef,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReferenceProblem {
+    /// The referenced record does not exist.
+    Missing,
+    /// The referenced record exists but was never filled in.
+    Empty,
+}
+
+/// A field of one record pointing at another record that is not usable.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReferenceIssue {
+    pub owner: RecordRef,
+    /// Dotted path of the referencing field, as used by `set_field`.
+    pub field: String,
+    pub target: RecordRef,
+    pub problem: ReferenceProblem,
+}
+
+impl fmt::Display for ReferenceIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let problem = match self.problem {
+            ReferenceProblem::Missing => "does not exist",
+            ReferenceProblem::Empty => "has no name",
+        };
+
+        write!(
+            f,
+            "{} {}: {} {}",
+            self.owner, self.field, self.target, problem
+        )
+    }
+}
+
+/// Every record of a project, used to check and rewrite the references
+/// between them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DataSet {
+    pub items: Vec<ItemData>,
+    pub npcs: Vec<NpcData>,
+    pub shops: Vec<ShopData>,
+}
+
+impl DataSet {
+    /// Reads every record without changing any file. Records that can not be
+    /// read are left at default, `datatool validate` reports those.
+    pub fn load(root: &Path, source: DataSource) -> Self {
+        Self {
+            items: Self::load_all(root, source),
+            npcs: Self::load_all(root, source),
+            shops: Self::load_all(root, source),
+        }
+    }
+
+    /// Reads every record of one kind the same way.
+    pub fn load_all<T: GameData>(root: &Path, source: DataSource) -> Vec<T> {
+        (0..T::count(root))
+            .map(|id| T::load_record(root, id, source).unwrap_or_default())
+            .collect()
+    }
+
+    /// Reads every record, failing on the first one that can not be read.
+    /// Used before writing records back, where a default would replace them.
+    pub fn load_checked(root: &Path, source: DataSource) -> Result<Self, String> {
+        Ok(Self {
+            items: Self::load_all_checked(root, source)?,
+            npcs: Self::load_all_checked(root, source)?,
+            shops: Self::load_all_checked(root, source)?,
+        })
+    }
+
+    fn load_all_checked<T: GameData>(root: &Path, source: DataSource) -> Result<Vec<T>, String> {
+        (0..T::count(root))
+            .map(|id| {
+                T::load_record(root, id, source)
+                    .map_err(|e| format!("Could not read {} {}, err {}", T::NAME, id, e))
+            })
+            .collect()
+    }
+
+    pub fn name_of(&self, record: RecordRef) -> Option<&str> {
+        match record.kind {
+            RecordKind::Item => self.items.get(record.id).map(|data| data.name.as_str()),
+            RecordKind::Npc => self.npcs.get(record.id).map(|data| data.name.as_str()),
+            RecordKind::Shop => self.shops.get(record.id).map(|data| data.name.as_str()),
+        }
+    }
+
+    /// Every shop item, npc drop and npc enemy reference. Unused shop slots
+    /// and drops without an amount are skipped.
+    pub fn references(&self) -> Vec<Reference> {
+        self.walk_references(true)
+    }
+
+    /// Every reference field, including unused shop slots and drops without
+    /// an amount. A renumber rewrites these too, so they still point at the
+    /// same record once they are put to use.
+    pub fn all_references(&self) -> Vec<Reference> {
+        self.walk_references(false)
+    }
+
+    fn walk_references(&self, used_only: bool) -> Vec<Reference> {
+        let mut references = Vec::new();
+
+        for (id, shop) in self.shops.iter().enumerate() {
+            let owner = RecordRef::new(RecordKind::Shop, id);
+            let slots = if used_only {
+                shop.max_item as usize
+            } else {
+                shop.item.len()
+            };
+
+            for (slot, item) in shop.item.iter().enumerate().take(slots) {
+                references.push(Reference {
+                    owner,
+                    field: format!("item.{}.index", slot),
+                    target: RecordRef::new(RecordKind::Item, item.index as usize),
+                });
+            }
+        }
+
+        for (id, npc) in self.npcs.iter().enumerate() {
+            let owner = RecordRef::new(RecordKind::Npc, id);
+
+            for (drop_id, drop) in npc.drops.iter().enumerate() {
+                for (slot, item) in drop.items.iter().enumerate() {
+                    if used_only && item.amount == 0 {
+                        continue;
+                    }
+
+                    references.push(Reference {
+                        owner,
+                        field: format!("drops.{}.items.{}.item", drop_id, slot),
+                        target: RecordRef::new(RecordKind::Item, item.item as usize),
+                    });
+                }
+            }
+
+            for (slot, enemy) in npc.enemies.iter().enumerate() {
+                references.push(Reference {
+                    owner,
+                    field: format!("enemies.{}", slot),
+                    target: RecordRef::new(RecordKind::Npc, *enemy as usize),
+                });
+            }
+        }
+
+        references
+    }
+
+    /// Finds references pointing at records that do not exist or have no
+    /// name.
+    pub fn check_references(&self) -> Vec<ReferenceIssue> {
+        self.references()
+            .into_iter()
+            .filter_map(|reference| {
+                let problem = match self.name_of(reference.target) {
+                    None => ReferenceProblem::Missing,
+                    Some(name) if name.trim().is_empty() => ReferenceProblem::Empty,
+                    Some(_) => return None,
+                };
+
+                Some(ReferenceIssue {
+                    owner: reference.owner,
+                    field: reference.field,
+                    target: reference.target,
+                    problem,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Record types a `DataSet` holds, so code working on any kind can find its
+/// records within one.
+pub trait SetRecord: GameData + PartialEq {
+    const KIND: RecordKind;
+
+    fn name(&self) -> &str;
+
+    fn of_set_mut(set: &mut DataSet) -> &mut Vec<Self>;
+}
+
+impl SetRecord for ItemData {
+    const KIND: RecordKind = RecordKind::Item;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn of_set_mut(set: &mut DataSet) -> &mut Vec<Self> {
+        &mut set.items
+    }
+}
+
+impl SetRecord for NpcData {
+    const KIND: RecordKind = RecordKind::Npc;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn of_set_mut(set: &mut DataSet) -> &mut Vec<Self> {
+        &mut set.npcs
+    }
+}
+
+impl SetRecord for ShopData {
+    const KIND: RecordKind = RecordKind::Shop;
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn of_set_mut(set: &mut DataSet) -> &mut Vec<Self> {
+        &mut set.shops
+    }
+}
+
+/// Lookup from a record to the references pointing at it, along with the
+/// names of the records those references belong to.
+#[derive(Clone, Debug, Default)]
+pub struct ReverseIndex {
+    used_by: HashMap<RecordRef, Vec<Reference>>,
+    names: HashMap<RecordRef, String>,
+}
+
+impl ReverseIndex {
+    pub fn new(set: &DataSet) -> Self {
+        let mut index = Self::default();
+
+        for reference in set.references() {
+            index
+                .names
+                .entry(reference.owner)
+                .or_insert_with(|| set.name_of(reference.owner).unwrap_or_default().to_owned());
+            index
+                .used_by
+                .entry(reference.target)
+                .or_default()
+                .push(reference);
+        }
+
+        index
+    }
+
+    pub fn used_by(&self, record: RecordRef) -> &[Reference] {
+        self.used_by.get(&record).map_or(&[], Vec::as_slice)
+    }
+
+    /// Name of a record owning a reference.
+    pub fn name_of(&self, record: RecordRef) -> &str {
+        self.names.get(&record).map_or("", String::as_str)
+    }
+}
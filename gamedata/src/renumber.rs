@@ -0,0 +1,647 @@
+use crate::*;
+use serde_json::Value;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+};
+
+/// New ids for the records of one kind.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Renumber {
+    pub kind: RecordKind,
+    /// Old id of the record placed at each id.
+    order: Vec<usize>,
+    /// New id of each record by its old id.
+    ids: Vec<usize>,
+}
+
+impl Renumber {
+    /// Gives records of a kind new ids. Each pair is an old and a new id,
+    /// the records not listed keep their order and fill the ids left over.
+    pub fn new(kind: RecordKind, count: usize, pairs: &[(usize, usize)]) -> Result<Self, String> {
+        let mut order = vec![None; count];
+        let mut listed = vec![false; count];
+
+        for &(old, new) in pairs {
+            if let Some(id) = [old, new].into_iter().find(|id| *id >= count) {
+                return Err(format!("{} does not exist", RecordRef::new(kind, id)));
+            }
+
+            if listed[old] {
+                return Err(format!(
+                    "{} is given more than one id",
+                    RecordRef::new(kind, old)
+                ));
+            }
+
+            if order[new].is_some() {
+                return Err(format!(
+                    "More than one record is moved to {}",
+                    RecordRef::new(kind, new)
+                ));
+            }
+
+            listed[old] = true;
+            order[new] = Some(old);
+        }
+
+        let mut rest = (0..count).filter(|id| !listed[*id]);
+        let order: Vec<usize> = order
+            .into_iter()
+            .map(|old| old.or_else(|| rest.next()))
+            .collect::<Option<_>>()
+            .ok_or_else(|| "Every id has to be used once".to_owned())?;
+
+        let mut ids = vec![0; count];
+
+        for (new, old) in order.iter().enumerate() {
+            ids[*old] = new;
+        }
+
+        Ok(Self { kind, order, ids })
+    }
+
+    /// Moves the records `from..=through` so the first of them ends up at
+    /// `to`, shifting the records in between to make room.
+    pub fn moved(
+        kind: RecordKind,
+        count: usize,
+        from: usize,
+        through: usize,
+        to: usize,
+    ) -> Result<Self, String> {
+        if through < from {
+            return Err(format!("{} comes before {}", through, from));
+        }
+
+        let len = through - from + 1;
+
+        if to + len > count {
+            return Err(format!(
+                "{} records starting at {} do not fit within the {} records",
+                len,
+                RecordRef::new(kind, to),
+                count
+            ));
+        }
+
+        let pairs: Vec<_> = (0..len).map(|i| (from + i, to + i)).collect();
+        Self::new(kind, count, &pairs)
+    }
+
+    /// Exchanges the ids of two records.
+    pub fn swapped(kind: RecordKind, count: usize, a: usize, b: usize) -> Result<Self, String> {
+        if a == b {
+            return Self::new(kind, count, &[(a, b)]);
+        }
+
+        Self::new(kind, count, &[(a, b), (b, a)])
+    }
+
+    /// New id of a record. Ids past the last record, such as those of
+    /// broken references, stay the same.
+    pub fn new_id(&self, old: usize) -> usize {
+        self.ids.get(old).copied().unwrap_or(old)
+    }
+
+    /// Old and new id of every record that changes id.
+    pub fn moves(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.ids
+            .iter()
+            .enumerate()
+            .map(|(old, new)| (old, *new))
+            .filter(|(old, new)| old != new)
+    }
+
+    fn reorder<T: Clone>(&self, records: &[T]) -> Result<Vec<T>, String> {
+        if records.len() != self.order.len() {
+            return Err(format!(
+                "Expected {} records of kind {} but found {}",
+                self.order.len(),
+                self.kind,
+                records.len()
+            ));
+        }
+
+        Ok(self.order.iter().map(|old| records[*old].clone()).collect())
+    }
+}
+
+/// A single change made by a renumber, listed before it is applied.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenumberChange {
+    /// A renumbered record taking its new id.
+    Moved {
+        record: RecordRef,
+        name: String,
+        to: usize,
+    },
+    /// A reference following the record it points at.
+    Rewritten { reference: Reference, to: usize },
+}
+
+impl fmt::Display for RenumberChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RenumberChange::Moved { record, name, to } => {
+                write!(f, "{} ({}) becomes {}", record, name, to)
+            }
+            RenumberChange::Rewritten { reference, to } => write!(
+                f,
+                "{} {}: {} becomes {}",
+                reference.owner, reference.field, reference.target.id, to
+            ),
+        }
+    }
+}
+
+/// A renumber worked out against every record of a project, along with the
+/// references it rewrites. Nothing is written until it is applied.
+#[derive(Clone, Debug)]
+pub struct RenumberPlan {
+    pub renumber: Renumber,
+    pub before: DataSet,
+    pub after: DataSet,
+    pub changes: Vec<RenumberChange>,
+}
+
+impl RenumberPlan {
+    pub fn new(before: DataSet, renumber: Renumber) -> Result<Self, String> {
+        let mut after = before.clone();
+        let mut changes = Vec::new();
+
+        match renumber.kind {
+            RecordKind::Item => after.items = renumber.reorder(&before.items)?,
+            RecordKind::Npc => after.npcs = renumber.reorder(&before.npcs)?,
+            RecordKind::Shop => after.shops = renumber.reorder(&before.shops)?,
+        }
+
+        for (old, to) in renumber.moves() {
+            let record = RecordRef::new(renumber.kind, old);
+
+            changes.push(RenumberChange::Moved {
+                record,
+                name: before.name_of(record).unwrap_or_default().to_owned(),
+                to,
+            });
+        }
+
+        for reference in before.all_references() {
+            if reference.target.kind != renumber.kind {
+                continue;
+            }
+
+            let to = renumber.new_id(reference.target.id);
+
+            if to == reference.target.id {
+                continue;
+            }
+
+            let mut owner = reference.owner;
+
+            // Npc enemies live within the records being renumbered.
+            if owner.kind == renumber.kind {
+                owner.id = renumber.new_id(owner.id);
+            }
+
+            after.set_reference(owner, &reference.field, to)?;
+            changes.push(RenumberChange::Rewritten { reference, to });
+        }
+
+        Ok(Self {
+            renumber,
+            before,
+            after,
+            changes,
+        })
+    }
+
+    /// Whether the records on disk are still the ones this plan was worked
+    /// out against.
+    pub fn is_current(&self, root: &Path, source: DataSource) -> Result<bool, String> {
+        Ok(DataSet::load_checked(root, source)? == self.before)
+    }
+
+    /// Saves every record the renumber changes and moves the backup folders
+    /// of the renumbered records along with them, returning how many records
+    /// were saved. Should any of it fail the folders are moved back and the
+    /// records already written are saved back as they were, so the project
+    /// is not left half renumbered.
+    pub fn apply(&self, root: &Path, save_json: bool) -> Result<usize, String> {
+        let mut written = Vec::new();
+        let mut moved = Vec::new();
+
+        let result = save_changed(
+            root,
+            RecordKind::Item,
+            &self.after.items,
+            &self.before.items,
+            save_json,
+            &mut written,
+        )
+        .and_then(|()| {
+            save_changed(
+                root,
+                RecordKind::Npc,
+                &self.after.npcs,
+                &self.before.npcs,
+                save_json,
+                &mut written,
+            )
+        })
+        .and_then(|()| {
+            save_changed(
+                root,
+                RecordKind::Shop,
+                &self.after.shops,
+                &self.before.shops,
+                save_json,
+                &mut written,
+            )
+        })
+        .and_then(|()| match self.renumber.kind {
+            RecordKind::Item => move_backups::<ItemData>(root, &self.renumber, &mut moved),
+            RecordKind::Npc => move_backups::<NpcData>(root, &self.renumber, &mut moved),
+            RecordKind::Shop => move_backups::<ShopData>(root, &self.renumber, &mut moved),
+        });
+
+        if let Err(mut error) = result {
+            for (from, to) in moved.iter().rev() {
+                if let Err(e) = fs::rename(to, from) {
+                    error.push_str(&format!(
+                        ", could not move {} back, Err {:?}",
+                        to.display(),
+                        e
+                    ));
+                }
+            }
+
+            for record in written {
+                if let Err(e) = self.before.save_record(root, record, save_json) {
+                    error.push_str(&format!(", could not put back {}, Err {}", record, e));
+                }
+            }
+
+            return Err(error);
+        }
+
+        Ok(written.len())
+    }
+}
+
+fn save_changed<T: GameData + PartialEq>(
+    root: &Path,
+    kind: RecordKind,
+    after: &[T],
+    before: &[T],
+    save_json: bool,
+    written: &mut Vec<RecordRef>,
+) -> Result<(), String> {
+    for (id, (after, before)) in after.iter().zip(before).enumerate() {
+        if after == before {
+            continue;
+        }
+
+        after
+            .save(root, id, save_json)
+            .map_err(|e| format!("Could not save {} {}, err {}", T::NAME, id, e))?;
+        written.push(RecordRef::new(kind, id));
+    }
+
+    Ok(())
+}
+
+/// Moves the backup folder of every renumbered record to its new id. The
+/// folders are first put aside within `backups/renumber`, as the new ids are
+/// taken by each other. Every move made is kept in `moved` to undo them.
+fn move_backups<T: GameData>(
+    root: &Path,
+    renumber: &Renumber,
+    moved: &mut Vec<(PathBuf, PathBuf)>,
+) -> Result<(), String> {
+    let aside = T::folder(root).join("backups").join("renumber");
+    let mut put_aside = Vec::new();
+
+    for (old, new) in renumber.moves() {
+        let from = T::backup_folder(root, old);
+
+        if !from.is_dir() {
+            continue;
+        }
+
+        fs::create_dir_all(&aside)
+            .map_err(|e| format!("Failed to create {}, Err {:?}", aside.display(), e))?;
+        move_folder(&from, &aside.join(old.to_string()), moved)?;
+        put_aside.push((old, new));
+    }
+
+    for (old, new) in put_aside {
+        move_folder(
+            &aside.join(old.to_string()),
+            &T::backup_folder(root, new),
+            moved,
+        )?;
+    }
+
+    let _ = fs::remove_dir(&aside);
+    Ok(())
+}
+
+fn move_folder(from: &Path, to: &Path, moved: &mut Vec<(PathBuf, PathBuf)>) -> Result<(), String> {
+    fs::rename(from, to).map_err(|e| {
+        format!(
+            "Failed to move {} to {}, Err {:?}",
+            from.display(),
+            to.display(),
+            e
+        )
+    })?;
+    moved.push((from.to_owned(), to.to_owned()));
+    Ok(())
+}
+
+fn set_in<T: GameData>(
+    records: &mut [T],
+    id: usize,
+    field: &str,
+    value: Value,
+) -> Result<(), String> {
+    let record = records
+        .get_mut(id)
+        .ok_or_else(|| format!("{} {} does not exist", T::NAME, id))?;
+
+    *record = set_field(record, field, value)?;
+    Ok(())
+}
+
+fn save_in<T: GameData>(
+    records: &[T],
+    root: &Path,
+    id: usize,
+    save_json: bool,
+) -> Result<(), String> {
+    records
+        .get(id)
+        .ok_or_else(|| format!("{} {} does not exist", T::NAME, id))?
+        .save(root, id, save_json)
+}
+
+impl DataSet {
+    /// Points a reference field of a record at another id.
+    fn set_reference(&mut self, owner: RecordRef, field: &str, id: usize) -> Result<(), String> {
+        let value = Value::from(id);
+
+        match owner.kind {
+            RecordKind::Item => set_in(&mut self.items, owner.id, field, value),
+            RecordKind::Npc => set_in(&mut self.npcs, owner.id, field, value),
+            RecordKind::Shop => set_in(&mut self.shops, owner.id, field, value),
+        }
+    }
+
+    fn save_record(&self, root: &Path, record: RecordRef, save_json: bool) -> Result<(), String> {
+        match record.kind {
+            RecordKind::Item => save_in(&self.items, root, record.id, save_json),
+            RecordKind::Npc => save_in(&self.npcs, root, record.id, save_json),
+            RecordKind::Shop => save_in(&self.shops, root, record.id, save_json),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{fs, path::PathBuf};
+
+    fn item(name: &str) -> ItemData {
+        ItemData {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Three items, a shop selling items 0 and 1 with an unused slot
+    /// pointing at item 1, and an npc dropping item 1 and fighting npc 1.
+    fn data_set() -> DataSet {
+        let mut shop = ShopData {
+            name: "Shop".into(),
+            max_item: 2,
+            ..Default::default()
+        };
+        shop.item[0].index = 0;
+        shop.item[1].index = 1;
+        shop.item[2].index = 1;
+
+        let mut npc = NpcData {
+            name: "Slime".into(),
+            enemies: vec![1],
+            ..Default::default()
+        };
+        npc.drops[0].items[0] = DropItem { item: 1, amount: 2 };
+        npc.drops[0].items[1] = DropItem { item: 2, amount: 0 };
+
+        DataSet {
+            items: vec![item("a"), item("b"), item("c")],
+            npcs: vec![
+                npc,
+                NpcData {
+                    name: "Bat".into(),
+                    ..Default::default()
+                },
+            ],
+            shops: vec![shop],
+        }
+    }
+
+    fn root(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "ascending_renumber_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join(ItemData::FOLDER)).unwrap();
+        root
+    }
+
+    #[test]
+    fn new_fills_unlisted_ids_in_order() {
+        let renumber = Renumber::new(RecordKind::Item, 4, &[(3, 0)]).unwrap();
+
+        assert_eq!(
+            (0..4).map(|id| renumber.new_id(id)).collect::<Vec<_>>(),
+            [1, 2, 3, 0]
+        );
+        assert_eq!(renumber.new_id(9), 9);
+    }
+
+    #[test]
+    fn new_rejects_bad_pairs() {
+        assert!(Renumber::new(RecordKind::Item, 3, &[(3, 0)]).is_err());
+        assert!(Renumber::new(RecordKind::Item, 3, &[(0, 1), (0, 2)]).is_err());
+        assert!(Renumber::new(RecordKind::Item, 3, &[(0, 1), (2, 1)]).is_err());
+    }
+
+    #[test]
+    fn moved_shifts_the_records_in_between() {
+        let renumber = Renumber::moved(RecordKind::Item, 5, 0, 1, 3).unwrap();
+
+        assert_eq!(
+            (0..5).map(|id| renumber.new_id(id)).collect::<Vec<_>>(),
+            [3, 4, 0, 1, 2]
+        );
+        assert!(Renumber::moved(RecordKind::Item, 5, 0, 1, 4).is_err());
+        assert!(Renumber::moved(RecordKind::Item, 5, 2, 1, 0).is_err());
+    }
+
+    #[test]
+    fn swapped_exchanges_two_ids() {
+        let renumber = Renumber::swapped(RecordKind::Item, 3, 0, 2).unwrap();
+
+        assert_eq!(renumber.moves().collect::<Vec<_>>(), [(0, 2), (2, 0)]);
+        assert_eq!(
+            Renumber::swapped(RecordKind::Item, 3, 1, 1)
+                .unwrap()
+                .moves()
+                .count(),
+            0
+        );
+    }
+
+    #[test]
+    fn plan_rewrites_every_reference() {
+        let renumber = Renumber::swapped(RecordKind::Item, 3, 1, 2).unwrap();
+        let plan = RenumberPlan::new(data_set(), renumber).unwrap();
+        let shop = &plan.after.shops[0];
+        let drop = &plan.after.npcs[0].drops[0];
+
+        assert_eq!(plan.after.items[1].name, "c");
+        assert_eq!(plan.after.items[2].name, "b");
+        assert_eq!(
+            shop.item[..3]
+                .iter()
+                .map(|slot| slot.index)
+                .collect::<Vec<_>>(),
+            [0, 2, 2]
+        );
+        assert_eq!(drop.items[0].item, 2);
+        assert_eq!(drop.items[1].item, 1);
+    }
+
+    #[test]
+    fn plan_rewrites_enemies_of_moved_npcs() {
+        let renumber = Renumber::swapped(RecordKind::Npc, 2, 0, 1).unwrap();
+        let plan = RenumberPlan::new(data_set(), renumber).unwrap();
+
+        assert_eq!(plan.after.npcs[1].name, "Slime");
+        assert_eq!(plan.after.npcs[1].enemies, [0]);
+    }
+
+    #[test]
+    fn apply_saves_the_changed_records() {
+        let root = root("apply");
+        let mut set = data_set();
+        set.npcs.clear();
+        set.shops.clear();
+
+        let renumber = Renumber::swapped(RecordKind::Item, 3, 0, 2).unwrap();
+        let plan = RenumberPlan::new(set, renumber).unwrap();
+
+        assert_eq!(plan.apply(&root, false), Ok(2));
+        assert_eq!(ItemData::load_file(&root, 0).unwrap().0.name, "c");
+        assert_eq!(ItemData::load_file(&root, 2).unwrap().0.name, "a");
+        assert!(!ItemData::bin_path(&root, 1).exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn apply_moves_backups_with_their_records() {
+        let root = root("backups");
+        let mut set = data_set();
+        set.npcs.clear();
+        set.shops.clear();
+
+        for (id, item) in set.items.iter().enumerate() {
+            item.save(&root, id, false).unwrap();
+        }
+
+        // Saving twice leaves each record with a backup of itself.
+        for (id, item) in set.items.iter().enumerate() {
+            item.save(&root, id, false).unwrap();
+        }
+
+        let renumber = Renumber::swapped(RecordKind::Item, 3, 0, 2).unwrap();
+        let plan = RenumberPlan::new(set, renumber).unwrap();
+
+        assert_eq!(plan.apply(&root, false), Ok(2));
+
+        for (id, name) in [(0, "c"), (1, "b"), (2, "a")] {
+            assert!(ItemData::backups(&root, id)
+                .iter()
+                .any(|backup| ItemData::load_backup(backup).unwrap().name == name));
+        }
+
+        assert!(!ItemData::folder(&root)
+            .join("backups")
+            .join("renumber")
+            .exists());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn apply_moves_backups_back_on_failure() {
+        let root = root("backups_rollback");
+        let mut set = data_set();
+        set.npcs.clear();
+        set.shops.clear();
+
+        for _ in 0..2 {
+            for (id, item) in set.items.iter().enumerate() {
+                item.save(&root, id, false).unwrap();
+            }
+        }
+
+        // Taking the place the backups of item 2 are put aside in makes the
+        // move fail after those of item 0 were moved.
+        let blocker = ItemData::folder(&root)
+            .join("backups")
+            .join("renumber")
+            .join("2");
+        fs::create_dir_all(blocker.join("taken")).unwrap();
+
+        let renumber = Renumber::swapped(RecordKind::Item, 3, 0, 2).unwrap();
+        let plan = RenumberPlan::new(set, renumber).unwrap();
+
+        assert!(plan.apply(&root, false).is_err());
+        assert_eq!(ItemData::load_file(&root, 0).unwrap().0.name, "a");
+        assert_eq!(ItemData::load_file(&root, 2).unwrap().0.name, "c");
+        assert!(!blocker.with_file_name("0").exists());
+        assert!(ItemData::backups(&root, 0)
+            .iter()
+            .any(|backup| ItemData::load_backup(backup).unwrap().name == "a"));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn apply_puts_back_written_records_on_failure() {
+        let root = root("rollback");
+        let set = data_set();
+
+        for (id, item) in set.items.iter().enumerate() {
+            item.save(&root, id, false).unwrap();
+        }
+
+        // There are no npc or shop folders, so rewriting the npc drop fails
+        // after the items were already written.
+        let renumber = Renumber::swapped(RecordKind::Item, 3, 0, 1).unwrap();
+        let plan = RenumberPlan::new(set, renumber).unwrap();
+
+        assert!(plan.apply(&root, false).is_err());
+        assert_eq!(ItemData::load_file(&root, 0).unwrap().0.name, "a");
+        assert_eq!(ItemData::load_file(&root, 1).unwrap().0.name, "b");
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,66 @@
+use crate::*;
+use std::fmt;
+
+/// Where a record that failed to load was restored from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RecoverySource {
+    Json,
+    Bin,
+    Default,
+}
+
+impl fmt::Display for RecoverySource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RecoverySource::Json => f.write_str("json copy"),
+            RecoverySource::Bin => f.write_str("bin file"),
+            RecoverySource::Default => f.write_str("default values"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LoadIssue {
+    pub name: &'static str,
+    pub id: usize,
+    pub error: String,
+    /// Where the broken bin file was moved to, if it existed.
+    pub quarantined: Option<String>,
+    pub source: RecoverySource,
+}
+
+impl fmt::Display for LoadIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {}: recovered from {} ({})",
+            self.name, self.id, self.source, self.error
+        )?;
+
+        if let Some(path) = &self.quarantined {
+            write!(f, ", broken file moved to {}", path)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Records that could not be loaded normally during `GameData::load_files`.
+#[derive(Clone, Debug, Default)]
+pub struct LoadReport {
+    pub issues: Vec<LoadIssue>,
+    /// Records whose bin file and json copy disagree. These are loaded from
+    /// the configured source until one side is chosen.
+    pub conflicts: Vec<Conflict>,
+}
+
+impl LoadReport {
+    /// True when there are no issues, conflicts are checked separately.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.issues.iter().map(|issue| issue.to_string()).collect()
+    }
+}
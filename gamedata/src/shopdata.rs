@@ -0,0 +1,32 @@
+use crate::*;
+use ascending_types::*;
+use serde::{Deserialize, Serialize};
+use speedy::{Readable, Writable};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, Educe, Readable, Writable)]
+#[educe(Default)]
+pub struct ShopItem {
+    pub index: u16,
+    pub amount: u16,
+    pub price: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Educe, Readable, Writable)]
+#[educe(Default)]
+pub struct ShopData {
+    pub name: String,
+    pub max_item: u16,
+    pub item: [ShopItem; MAX_SHOP_ITEM],
+}
+
+impl Record for ShopData {
+    const FOLDER: &'static str = "shops";
+    const MAX: usize = MAX_SHOPS;
+    const NAME: &'static str = "Shop";
+    const VERSION: u16 = 1;
+
+    fn migrations() -> Migrations<Self> {
+        // Version 0 bins have no header but share the version 1 layout.
+        Migrations::new().register(0, read_unchanged)
+    }
+}
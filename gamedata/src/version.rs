@@ -0,0 +1,67 @@
+use speedy::{LittleEndian, Readable};
+
+/// Bytes every versioned bin file starts with. Files without them are treated
+/// as version 0, the layout used before bin files carried a header.
+pub const BIN_MAGIC: [u8; 4] = *b"ASDB";
+/// Size of the magic bytes plus the little endian u16 version.
+pub const BIN_HEADER_LEN: usize = BIN_MAGIC.len() + 2;
+
+/// Reads the bytes of an older layout and upgrades them to the current record.
+pub type Migration<T> = fn(&[u8]) -> Result<T, String>;
+
+/// Lookup of the older bin layouts a record type can still read.
+///
+/// When a field gets added to a record, copy the old struct as `{Name}V{n}`,
+/// bump `Record::VERSION` and register a migration for `n` converting the
+/// old struct into the new one.
+pub struct Migrations<T> {
+    list: Vec<(u16, Migration<T>)>,
+}
+
+impl<T> Default for Migrations<T> {
+    fn default() -> Self {
+        Self { list: Vec::new() }
+    }
+}
+
+impl<T> Migrations<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn register(mut self, version: u16, migration: Migration<T>) -> Self {
+        self.list.retain(|(v, _)| *v != version);
+        self.list.push((version, migration));
+        self
+    }
+
+    pub fn get(&self, version: u16) -> Option<Migration<T>> {
+        self.list
+            .iter()
+            .find(|(v, _)| *v == version)
+            .map(|(_, migration)| *migration)
+    }
+}
+
+/// Migration for layouts that are byte compatible with the current record.
+pub fn read_unchanged<T>(bytes: &[u8]) -> Result<T, String>
+where
+    T: for<'a> Readable<'a, LittleEndian>,
+{
+    T::read_from_buffer(bytes).map_err(|e| format!("Speedy Read Error {:?}", e))
+}
+
+/// Splits a bin file into its layout version and record bytes.
+pub fn split_header(bytes: &[u8]) -> (u16, &[u8]) {
+    if bytes.len() >= BIN_HEADER_LEN && bytes[..BIN_MAGIC.len()] == BIN_MAGIC {
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        (version, &bytes[BIN_HEADER_LEN..])
+    } else {
+        (0, bytes)
+    }
+}
+
+pub fn write_header(version: u16, bytes: &mut Vec<u8>) {
+    bytes.extend_from_slice(&BIN_MAGIC);
+    bytes.extend_from_slice(&version.to_le_bytes());
+}
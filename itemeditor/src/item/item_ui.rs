@@ -1,88 +1,125 @@
-use crate::{item::*, load_config, ConfigData};
+use crate::{item::*, CONFIG_FILE};
+use ascending_data::*;
 use ascending_types::*;
 use ascending_ui::*;
+use std::sync::{Arc, Mutex};
 
 use iced::{
-    widget::{column, scrollable, Container},
-    Color, Element, Length,
+    widget::{column, row, scrollable, Column, Container},
+    Color, Element, Length, Task,
 };
 
-#[allow(dead_code)]
-#[derive(Educe)]
-#[educe(Default)]
 pub struct ItemUI {
-    #[educe(Default(expression = Vec::with_capacity(MAX_ITEMS)))]
-    pub data: Vec<(ItemData, bool)>,
+    core: EditorCore<ItemData>,
     menu: ItemUiMenu,
     generic: ItemUiGeneric, //Generic Item Data.
     data_ui: ItemUiData,    //Item Generic Data Types.
-    currentid: usize,
-    config: ConfigData,
+    /// Meaning of the data fields per item type.
+    schema: ItemSchema,
+    sprite_gallery: SpriteGallery,
 }
 
 impl UiRenderer for ItemUI {
     type Message = Message;
-    fn update(&mut self, msg: Message) {
+
+    fn update(&mut self, msg: Message) -> Task<Message> {
+        let task = self.handle_message(msg);
+        let filters = &self.menu.filters;
+
+        self.core.refresh_records(|data| filters.passes(data));
+        self.core.track_unsaved();
+        task
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        self.layout()
+    }
+}
+
+impl ItemUI {
+    fn handle_message(&mut self, msg: Message) -> Task<Message> {
+        let id = self.core.currentid;
+        let before = self.core.data[id].0.clone();
+        let merge = matches!(msg, Message::NameInput(_) | Message::SoundInput(_));
+
         match msg {
-            Message::SaveAllButtonPress => {
-                self.save_all();
-                return;
-            }
-            Message::SaveButtonPress => {
-                if self.config.save_json {
-                    self.data[self.currentid]
-                        .0
-                        .save_file(self.currentid)
-                        .unwrap();
+            Message::Editor(msg) => {
+                let schema = &mut self.schema;
+                let task = self.core.update(msg, |config, _| {
+                    *schema = ItemSchema::load(&config.project.data)?;
+                    Ok(())
+                });
+
+                if self.core.take_refresh() {
+                    self.set_object_to_layout(self.core.currentid);
                 }
-                self.data[self.currentid]
-                    .0
-                    .save_bin_file(self.currentid)
-                    .unwrap();
-                return;
+
+                return task.map(Message::Editor);
             }
-            Message::RevertButtonPress => {
-                let item = ItemData::load_file(self.currentid).unwrap();
-                self.data[self.currentid].0 = item.0;
-                self.data[self.currentid].1 = false;
-                self.set_object_to_layout(self.currentid);
-                return;
+            Message::ShowSpriteGallery => {
+                self.sprite_gallery.open(
+                    &self.core.config.project.resources,
+                    self.core
+                        .data
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, (item, _))| *i != id && !item.name.is_empty())
+                        .map(|(_, (item, _))| i64::from(item.sprite)),
+                );
+                return Task::none();
             }
-            Message::ListSelect(data) => {
-                self.currentid = data.id;
-                self.menu.list_selected = Some(data);
-                self.set_object_to_layout(self.currentid);
-                return;
+            Message::SpriteGallery(msg) => {
+                let Some(sprite) = self
+                    .sprite_gallery
+                    .update(msg)
+                    .and_then(|sprite| u16::try_from(sprite).ok())
+                else {
+                    return Task::none();
+                };
+
+                self.generic.sprite_input.value = sprite;
+                self.core.current_mut().sprite = sprite;
+            }
+            Message::FilterType(item_type) => {
+                self.menu.filters.item_type = item_type;
+                self.core.filters_changed();
+                return Task::none();
+            }
+            Message::FilterLevel((id, input)) => {
+                self.menu.filters.set_level(id, &input);
+                self.core.filters_changed();
+                return Task::none();
+            }
+            Message::ClearFilters => {
+                self.menu.filters = ItemFilters::default();
+                self.core.filters_changed();
+                return Task::none();
             }
             Message::DataInput((i, data)) => {
                 self.data_ui.input[i].value = data.get_data();
-                self.data[self.currentid].0.data[i] = self.data_ui.input[i].value;
+                self.core.current_mut().data[i] = self.data_ui.input[i].value;
             }
             Message::TypeSelect(item_type) => {
                 self.generic.type_selected = Some(item_type);
-                self.data[self.currentid].0.itemtype = item_type;
+                self.core.current_mut().itemtype = item_type;
             }
             Message::NameInput(value) => {
                 if value.len() < 64 {
                     self.generic.txt_value = value;
-                    self.data[self.currentid]
-                        .0
-                        .name
-                        .clone_from(&self.generic.txt_value);
-                    self.menu.list[self.currentid]
+                    self.core
+                        .current_mut()
                         .name
                         .clone_from(&self.generic.txt_value);
-                    self.menu.list_selected = Some(self.menu.list[self.currentid].clone());
                 } else {
-                    return;
+                    return Task::none();
                 }
             }
             Message::SoundInput(value) => {
                 self.generic.sound_name = value;
                 if !self.generic.sound_name.is_empty() {
-                    self.data[self.currentid].0.sound_index = Some(self.generic.sound_name.clone());
+                    self.core.current_mut().sound_index = Some(self.generic.sound_name.clone());
                 } else {
-                    self.data[self.currentid].0.sound_index = None;
+                    self.core.current_mut().sound_index = None;
                 }
             }
             Message::GenericI32Input((id, data)) => {
@@ -90,9 +127,9 @@ impl UiRenderer for ItemUI {
                 if id == 0 {
                     self.generic.animation_input.value = data.get_data();
                     if value >= 0 {
-                        self.data[self.currentid].0.animation = Some(value as u32);
+                        self.core.current_mut().animation = Some(value as u32);
                     } else {
-                        self.data[self.currentid].0.animation = None;
+                        self.core.current_mut().animation = None;
                     }
                 }
             }
@@ -102,174 +139,192 @@ impl UiRenderer for ItemUI {
                 match id {
                     1 => {
                         self.generic.level_input.value = value;
-                        self.data[self.currentid].0.levelreq = value;
+                        self.core.current_mut().levelreq = value;
                     }
                     2 => {
                         self.generic.sound_input.value = value;
-                        self.data[self.currentid].0.soundid = value;
+                        self.core.current_mut().soundid = value;
                     }
                     3 => {
                         self.generic.stack_limit_input.value = value;
-                        self.data[self.currentid].0.stacklimit = value;
+                        self.core.current_mut().stacklimit = value;
                     }
                     4 => {
                         self.generic.sprite_input.value = value;
-                        self.data[self.currentid].0.sprite = value;
+                        self.core.current_mut().sprite = value;
                     }
                     5 => {
                         self.generic.type2.value = value;
-                        self.data[self.currentid].0.itemtype2 = value as u8;
+                        self.core.current_mut().itemtype2 = value as u8;
                     }
-                    _ => return,
+                    _ => return Task::none(),
                 }
             }
             Message::BasePriceInput((_, data)) => {
                 self.generic.base_price_input.value = data.get_data();
-                self.data[self.currentid].0.baseprice = self.generic.base_price_input.value;
+                self.core.current_mut().baseprice = self.generic.base_price_input.value;
             }
             Message::GenericBoolInput((id, data)) => {
                 let value = data.get_data();
                 match id {
                     0 => {
                         self.generic.breakable = value;
-                        self.data[self.currentid].0.breakable = value;
+                        self.core.current_mut().breakable = value;
                     }
                     1 => {
                         self.generic.repairable = value;
-                        self.data[self.currentid].0.repairable = value;
+                        self.core.current_mut().repairable = value;
                     }
                     2 => {
                         self.generic.stackable = value;
-                        self.data[self.currentid].0.stackable = value;
+                        self.core.current_mut().stackable = value;
                     }
                     _ => {}
                 }
             }
             Message::ChooseColor => {
                 self.generic.show_color = true;
-                return;
+                return Task::none();
             }
             Message::SubmitColor(color) => {
                 self.generic.color = color;
-                self.data[self.currentid].0.rgba.r = (color.r * 255.0) as i16;
-                self.data[self.currentid].0.rgba.g = (color.g * 255.0) as i16;
-                self.data[self.currentid].0.rgba.b = (color.b * 255.0) as i16;
-                self.data[self.currentid].0.rgba.a = (color.a * 255.0) as i16;
+                self.core.current_mut().rgba.r = (color.r * 255.0) as i16;
+                self.core.current_mut().rgba.g = (color.g * 255.0) as i16;
+                self.core.current_mut().rgba.b = (color.b * 255.0) as i16;
+                self.core.current_mut().rgba.a = (color.a * 255.0) as i16;
                 self.generic.show_color = false;
             }
             Message::CancelColor => {
                 self.generic.show_color = false;
-                return;
+                return Task::none();
             }
         }
 
-        self.data[self.currentid].1 = true;
-    }
+        self.core.edited(id, before, merge);
 
-    fn view(&self) -> Element<Message> {
-        self.layout()
+        Task::none()
     }
-}
-
-impl ItemUI {
-    pub fn new() -> Self {
-        let config: ConfigData = load_config();
 
+    /// Opens the project of the config, failing if its records can not be read.
+    pub fn new(
+        config: EditorConfig,
+        journal: Arc<Mutex<Journal<ItemData>>>,
+    ) -> Result<Self, String> {
+        let tools = EditorTools {
+            sprites: true,
+            referenced: true,
+        };
         let mut ui = ItemUI {
-            data: ItemData::load_files(config.save_json).unwrap(),
-            config,
-            ..Default::default()
+            core: EditorCore::new(config, CONFIG_FILE, journal, tools),
+            menu: ItemUiMenu::default(),
+            generic: ItemUiGeneric::default(),
+            data_ui: ItemUiData::default(),
+            schema: ItemSchema::default(),
+            sprite_gallery: SpriteGallery::new(PickerKind::Item),
         };
 
-        for (i, v) in ui.data.iter().enumerate() {
-            ui.menu.list.push(ListData::new(i, v.0.name.clone()));
-        }
-
-        ui.menu.list_selected = Some(ui.menu.list[0].clone());
-
         for i in 0..ItemTypes::Count as usize {
             ui.generic.type_list.push(ItemTypes::from_index(i));
         }
 
+        let schema = &mut ui.schema;
+        ui.core.load_project(|config, _| {
+            *schema = ItemSchema::load(&config.project.data)?;
+            Ok(())
+        })?;
         ui.set_object_to_layout(0);
-        ui
-    }
 
-    pub fn save_all(&mut self) {
-        for (i, v) in self.data.iter().enumerate() {
-            if !v.1 {
-                continue;
-            }
-
-            if self.config.save_json {
-                if let Err(e) = v.0.save_file(i) {
-                    println!("Could not save Item {}, err {}", i, e);
-                }
-            }
-            if let Err(e) = v.0.save_bin_file(i) {
-                println!("Could not save bin Item {}, err {}", i, e);
-            }
-        }
+        let filters = &ui.menu.filters;
+        ui.core.refresh_records(|data| filters.passes(data));
+        Ok(ui)
     }
 
     fn set_object_to_layout(&mut self, index: usize) {
+        let data = &self.core.data[index].0;
+
         for (id, control) in self.data_ui.input.iter_mut().enumerate() {
-            control.value = self.data[index].0.data[id];
+            control.value = data.data[id];
         }
 
-        self.generic.type_selected = Some(self.data[index].0.itemtype);
-        self.generic.txt_value.clone_from(&self.data[index].0.name);
-        self.generic.level_input.value = self.data[index].0.levelreq;
-        self.generic.sound_input.value = self.data[index].0.soundid;
-        self.generic.stack_limit_input.value = self.data[index].0.stacklimit;
-        self.generic.sprite_input.value = self.data[index].0.sprite;
-        self.generic.type2.value = self.data[index].0.itemtype2 as u16;
-        self.generic.base_price_input.value = self.data[index].0.baseprice;
-        self.generic.repairable = self.data[index].0.repairable;
-        self.generic.stackable = self.data[index].0.stackable;
-        self.generic.breakable = self.data[index].0.breakable;
+        self.generic.type_selected = Some(data.itemtype);
+        self.generic.txt_value.clone_from(&data.name);
+        self.generic.level_input.value = data.levelreq;
+        self.generic.sound_input.value = data.soundid;
+        self.generic.stack_limit_input.value = data.stacklimit;
+        self.generic.sprite_input.value = data.sprite;
+        self.generic.type2.value = data.itemtype2 as u16;
+        self.generic.base_price_input.value = data.baseprice;
+        self.generic.repairable = data.repairable;
+        self.generic.stackable = data.stackable;
+        self.generic.breakable = data.breakable;
 
-        if let Some(name) = self.data[index].0.sound_index.clone() {
+        if let Some(name) = data.sound_index.clone() {
             self.generic.sound_name = name;
         } else {
             self.generic.sound_name = String::new();
         }
 
-        if let Some(data) = self.data[index].0.animation {
+        if let Some(data) = data.animation {
             self.generic.animation_input.value = data as i32;
         } else {
             self.generic.animation_input.value = -1;
         }
 
         self.generic.color = Color::new(
-            f32::from(self.data[index].0.rgba.r) / 255.0,
-            f32::from(self.data[index].0.rgba.g) / 255.0,
-            f32::from(self.data[index].0.rgba.b) / 255.0,
-            f32::from(self.data[index].0.rgba.a) / 255.0,
+            f32::from(data.rgba.r) / 255.0,
+            f32::from(data.rgba.g) / 255.0,
+            f32::from(data.rgba.b) / 255.0,
+            f32::from(data.rgba.a) / 255.0,
         );
     }
 
-    fn layout(&self) -> Element<Message> {
+    fn layout(&self) -> Element<'_, Message> {
         let item_type = self.generic.type_selected.unwrap_or(ItemTypes::None);
 
         Container::new(
-            column![
-                self.menu.layout(),
-                scrollable(column![
-                    Container::new(
-                        self.generic
-                            .layout(self.generic.type_selected.unwrap_or(ItemTypes::None),)
-                    )
-                    .padding(5)
-                    .width(Length::Fill)
-                    .center_x(Length::Fill),
-                    Container::new(self.data_ui.layout(item_type))
-                        .padding(5)
-                        .width(Length::Fill)
-                        .center_x(Length::Fill)
-                ])
-            ]
-            .spacing(20),
+            Column::new()
+                .push_maybe(
+                    self.core
+                        .notices_layout()
+                        .map(|view| view.map(Message::Editor)),
+                )
+                .push(self.core.menu_layout().map(Message::Editor))
+                .push_maybe(
+                    self.core
+                        .panels_layout()
+                        .map(|view| view.map(Message::Editor)),
+                )
+                .push_maybe(
+                    self.sprite_gallery
+                        .view(i64::from(self.core.current().sprite))
+                        .map(|gallery| gallery.map(Message::SpriteGallery)),
+                )
+                .push(
+                    row![
+                        self.menu.list_layout(
+                            self.core.list_layout().map(Message::Editor),
+                            &self.generic.type_list
+                        ),
+                        scrollable(column![
+                            Container::new(self.generic.layout(
+                                self.generic.type_selected.unwrap_or(ItemTypes::None),
+                                &self.core.config.project.resources,
+                            ))
+                            .padding(5)
+                            .width(Length::Fill)
+                            .center_x(Length::Fill),
+                            Container::new(self.data_ui.layout(
+                                &self.schema.fields_of(item_type),
+                                &self.core.current().data,
+                            ))
+                            .padding(5)
+                            .width(Length::Fill)
+                            .center_x(Length::Fill)
+                        ])
+                    ]
+                    .spacing(10),
+                )
+                .spacing(20),
         )
         .width(Length::Fill)
         .height(Length::Fill)
@@ -1,10 +1,11 @@
 use crate::item::*;
 use arr_macro::arr;
+use ascending_data::*;
 use ascending_types::*;
 use ascending_ui::*;
 use iced::{
     alignment::Alignment,
-    widget::{column, text, Row, Rule},
+    widget::{column, container, text, tooltip, Row, Rule},
     Element, Length,
 };
 
@@ -12,36 +13,76 @@ use iced::{
 #[educe(Default)]
 pub struct ItemUiData {
     #[educe(Default(expression = arr![NumInput::new(0); 20]))]
-    pub input: [NumInput<i16, Message>; 20],
+    pub input: [NumInput<i16, Message>; ITEM_DATA_LEN],
 }
 
 impl ItemUiData {
-    pub fn layout(&self, item_type: ItemTypes) -> Element<Message> {
+    /// Inputs for the data fields the item schema lists for the item type,
+    /// bounded by their ranges. Values set outside of the schema are listed
+    /// so they are not missed.
+    pub fn layout(&self, fields: &[&DataField], data: &[i16]) -> Element<'_, Message> {
         let mut i: i32 = 0;
         let mut col = column![text("Data Inputs"), Rule::horizontal(0)]
             .spacing(6)
             .align_x(Alignment::Center)
             .width(Length::Shrink);
 
+        if fields.is_empty() {
+            col = col.push(text("The item schema has no data for this type."));
+        }
+
         let mut row = Row::new().spacing(12).align_y(Alignment::Start);
 
-        for (id, control) in self.input.iter().enumerate() {
+        for field in fields {
             if i == 6 {
                 i = 0;
                 col = col.push(row);
                 row = Row::new().spacing(12).align_y(Alignment::Start);
             }
 
-            row = row.push(
-                column![
-                    data_labels(id, item_type),
-                    control.view(id, i16::MIN, i16::MAX, 1, Message::DataInput, None)
-                ]
-                .spacing(5),
-            );
+            let input = column![
+                text(field.label.clone()),
+                self.input[field.index].view(
+                    field.index,
+                    field.min,
+                    field.max.saturating_add(1),
+                    1,
+                    Message::DataInput,
+                    None
+                )
+            ]
+            .spacing(5);
+
+            row = if field.tooltip.is_empty() {
+                row.push(input)
+            } else {
+                row.push(tooltip(
+                    input,
+                    container(text(field.tooltip.clone()))
+                        .style(container::rounded_box)
+                        .padding(5),
+                    tooltip::Position::Top,
+                ))
+            };
             i += 1;
         }
 
-        col.push(row).into()
+        let hidden: Vec<String> = data
+            .iter()
+            .enumerate()
+            .filter(|(index, value)| **value != 0 && !fields.iter().any(|f| f.index == *index))
+            .map(|(index, value)| format!("data.{} = {}", index, value))
+            .collect();
+
+        col = col.push(row);
+
+        if !hidden.is_empty() {
+            col = col.push(text(format!(
+                "Set outside of the item schema: {}",
+                hidden.join(", ")
+            )));
+        }
+
+        col.into()
     }
 }
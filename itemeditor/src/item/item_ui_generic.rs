@@ -1,4 +1,5 @@
 use crate::item::*;
+use ascending_data::SpriteFolder;
 use ascending_types::*;
 use ascending_ui::*;
 use iced::{
@@ -43,7 +44,7 @@ pub struct ItemUiGeneric {
 }
 
 impl ItemUiGeneric {
-    pub fn layout(&self, _item_type: ItemTypes) -> Element<Message> {
+    pub fn layout(&self, _item_type: ItemTypes, resources: &Path) -> Element<'_, Message> {
         let row0 = row![
             text_input("Name", &self.txt_value)
                 .on_input(Message::NameInput)
@@ -59,16 +60,16 @@ impl ItemUiGeneric {
 
         let sprite_value = self.sprite_input.value;
 
-        let image_path = format!("./resources/items/i{}.png", sprite_value);
+        let image_path = SpriteFolder::ITEMS.path(resources, i64::from(sprite_value));
 
         let row1 = row![column![
             "Item Sprite",
             row![
                 self.sprite_input
                     .view(4, 0, 1000, 1, Message::GenericInput, None),
-                if Path::new(&image_path).exists() {
+                if image_path.exists() {
                     container(
-                        Image::new(&image_path)
+                        Image::new(image_path)
                             .width(Length::Fixed(32.0))
                             .height(Length::Fixed(32.0)),
                     )
@@ -78,7 +79,8 @@ impl ItemUiGeneric {
                     Container::new("")
                         .width(Length::Fixed(32.0))
                         .height(Length::Fixed(32.0))
-                }
+                },
+                button("Gallery").on_press(Message::ShowSpriteGallery),
             ]
             .align_y(Alignment::Center)
             .spacing(6),
@@ -4,13 +4,15 @@ use iced::Color;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Message {
-    SaveButtonPress,
-    SaveAllButtonPress,
-    RevertButtonPress,
-    ListSelect(ListData),
+    Editor(EditorMessage),
+    FilterType(Option<ItemTypes>),
+    FilterLevel((usize, String)),
+    ClearFilters,
     TypeSelect(ItemTypes),
     DataInput((usize, NumInputMessage<i16>)),
     GenericInput((usize, NumInputMessage<u16>)),
+    ShowSpriteGallery,
+    SpriteGallery(GalleryMessage),
     GenericI32Input((usize, NumInputMessage<i32>)),
     BasePriceInput((usize, NumInputMessage<u64>)),
     GenericBoolInput((usize, CheckBoxMessage)),
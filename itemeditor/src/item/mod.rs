@@ -2,12 +2,10 @@ mod item_ui;
 mod item_ui_data;
 mod item_ui_generic;
 mod item_ui_menu;
-mod itemdata;
 mod messages;
 
 pub use item_ui::*;
 pub use item_ui_data::*;
 pub use item_ui_generic::*;
 pub use item_ui_menu::*;
-pub use itemdata::*;
 pub use messages::*;
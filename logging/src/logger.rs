@@ -36,7 +36,7 @@ impl Log for MyLogger {
         let msg = format!("{} - {}\n", record.level(), record.args());
 
         if self.print {
-            println!("{}", &msg);
+            println!("{}", msg);
         }
 
         let mut file = match File::options()
@@ -4,10 +4,14 @@ use iced_aw::time_picker::Time;
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    SaveButtonPress,
-    SaveAllButtonPress,
-    RevertButtonPress,
-    ListSelect(ListData),
+    Editor(EditorMessage),
+    ShowDropRates,
+    CloseDropRates,
+    DropKillsInput((usize, NumInputMessage<u32>)),
+    SimulateDrops,
+    FilterBehaviour(Option<AIBehavior>),
+    FilterLevel((usize, String)),
+    ClearFilters,
     GenericU8Input((usize, NumInputMessage<u8>)),
     GenericI32Input((usize, NumInputMessage<i32>)),
     GenericU32Input((usize, NumInputMessage<u32>)),
@@ -20,8 +24,14 @@ pub enum Message {
     CancelTime,
     BehaviourTypeSelect(AIBehavior),
     NameInput(String),
-    ItemDropSlotSelect(usize),
+    DropSharesInput((usize, NumInputMessage<u32>)),
+    DropItemInput((usize, NumInputMessage<u32>)),
+    DropAmountInput((usize, NumInputMessage<u32>)),
     EnemyListSelect(ListData),
+    ItemPicker(PickerMessage),
+    EnemyPicker(PickerMessage),
+    ShowSpriteGallery,
+    SpriteGallery(GalleryMessage),
     AddEnemy,
     RemoveEnemy,
     UpdateEnemy,
@@ -1,15 +1,17 @@
 mod messages;
+mod npc_drop_grid;
+mod npc_drop_rates;
 mod npc_enemies;
 mod npc_ui;
 mod npc_ui_generic;
 mod npc_ui_menu;
 mod npc_ui_settings;
-mod npcdata;
 
 pub use messages::*;
+pub use npc_drop_grid::*;
+pub use npc_drop_rates::*;
 pub use npc_enemies::*;
 pub use npc_ui::*;
 pub use npc_ui_generic::*;
 pub use npc_ui_menu::*;
 pub use npc_ui_settings::*;
-pub use npcdata::*;
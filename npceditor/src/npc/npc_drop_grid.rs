@@ -0,0 +1,169 @@
+use crate::npc::*;
+use ascending_data::*;
+use ascending_ui::*;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, container, row, text, Column, Container, Image, Row, Rule},
+    Element, Length,
+};
+use std::path::Path;
+
+const CELL_WIDTH: f32 = 150.0;
+
+#[derive(Educe)]
+#[educe(Default)]
+pub struct Items {
+    #[educe(Default(expression = NumInput::new(0)))]
+    pub item_id: NumInput<u32, Message>,
+    #[educe(Default(expression = NumInput::new(0)))]
+    pub amount: NumInput<u32, Message>,
+}
+
+/// Every drop slot of an npc at once, each with its shares, chance and the
+/// five items it drops. Inputs are numbered by slot, items by
+/// `slot * 5 + item`.
+#[derive(Educe)]
+#[educe(Default)]
+pub struct NpcDropGrid {
+    #[educe(Default(expression = NumInput::new(0)))]
+    pub free_shares: NumInput<u32, Message>,
+    #[educe(Default(expression = std::array::from_fn(|_| NumInput::new(0))))]
+    pub shares: [NumInput<u32, Message>; 10],
+    pub items: [[Items; 5]; 10],
+}
+
+impl NpcDropGrid {
+    pub fn set_drops(&mut self, npc: &NpcData) {
+        self.free_shares.value = npc.free_shares;
+
+        for (slot, drop) in npc.drops.iter().enumerate() {
+            self.shares[slot].value = drop.shares;
+
+            for (index, item) in drop.items.iter().enumerate() {
+                self.items[slot][index].item_id.value = item.item;
+                self.items[slot][index].amount.value = item.amount;
+            }
+        }
+    }
+
+    pub fn layout<'a>(
+        &'a self,
+        npc: &NpcData,
+        items: &[ItemData],
+        resources: &Path,
+    ) -> Element<'a, Message> {
+        let total = npc.total_shares();
+        let mut grid = Column::new().spacing(10);
+
+        grid = grid.push(
+            row![
+                text("Free Shares:"),
+                self.free_shares
+                    .view(20, 0, u32::MAX, 1, Message::GenericU32Input, None),
+                text(format!(
+                    "Nothing drops {:.2}%",
+                    npc.nothing_chance() * 100.0
+                )),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+        );
+
+        for (slot, drop) in npc.drops.iter().enumerate() {
+            let chance = match total {
+                0 => 0.0,
+                total => f64::from(drop.shares) / total as f64,
+            };
+            let has_items = drop.items.iter().any(|item| item.amount > 0);
+            let warning = match (drop.shares > 0, has_items) {
+                (true, false) => "Has shares but no items",
+                (false, true) => "Has items but no shares",
+                _ => "",
+            };
+
+            let header = column![
+                text(format!("Slot {}", slot)),
+                self.shares[slot].view(slot, 0, u32::MAX, 1, Message::DropSharesInput, None),
+                text(format!("{:.2}%", chance * 100.0)),
+                text(warning),
+            ]
+            .spacing(4)
+            .width(Length::Fixed(CELL_WIDTH));
+
+            let line = drop.items.iter().enumerate().fold(
+                Row::new().push(header).spacing(10),
+                |line, (index, item)| line.push(self.cell(slot, index, item, items, resources)),
+            );
+
+            grid = grid.push(line);
+        }
+
+        column![
+            row![
+                Rule::horizontal(0),
+                text("Item Drops:"),
+                Rule::horizontal(0),
+            ]
+            .spacing(10)
+            .align_y(Alignment::Center),
+            grid,
+        ]
+        .spacing(12)
+        .into()
+    }
+
+    fn cell<'a>(
+        &'a self,
+        slot: usize,
+        index: usize,
+        item: &DropItem,
+        items: &[ItemData],
+        resources: &Path,
+    ) -> Element<'a, Message> {
+        let id = slot * 5 + index;
+        let data = items.get(item.item as usize);
+
+        let name = match data {
+            _ if item.amount == 0 => "Empty".to_owned(),
+            Some(data) => data.name.clone(),
+            None => format!("Item {} is missing", item.item),
+        };
+
+        let image_path = data
+            .filter(|_| item.amount > 0)
+            .map(|data| SpriteFolder::ITEMS.path(resources, i64::from(data.sprite)));
+
+        let icon = match image_path {
+            Some(path) if path.exists() => container(
+                Image::new(path)
+                    .width(Length::Fixed(32.0))
+                    .height(Length::Fixed(32.0)),
+            ),
+            _ => Container::new(""),
+        }
+        .width(Length::Fixed(32.0))
+        .height(Length::Fixed(32.0));
+
+        column![
+            row![icon, text(name)].spacing(6).align_y(Alignment::Center),
+            row![
+                self.items[slot][index].item_id.view(
+                    id,
+                    0,
+                    u32::MAX,
+                    1,
+                    Message::DropItemInput,
+                    None
+                ),
+                button("Pick").on_press(Message::ItemPicker(PickerMessage::Open(id))),
+            ]
+            .spacing(4),
+            self.items[slot][index]
+                .amount
+                .view(id, 0, u32::MAX, 1, Message::DropAmountInput, None),
+        ]
+        .spacing(4)
+        .width(Length::Fixed(CELL_WIDTH))
+        .into()
+    }
+}
@@ -0,0 +1,181 @@
+use crate::npc::*;
+use ascending_data::*;
+use ascending_ui::*;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, row, text},
+    Element,
+};
+
+/// Drop chances of the selected npc along with a simulation of many kills,
+/// valued by the base price of the dropped items.
+#[derive(Educe)]
+#[educe(Default)]
+pub struct NpcDropRates {
+    pub show: bool,
+    #[educe(Default(expression = NumInput::new(10000)))]
+    pub kills_input: NumInput<u32, Message>,
+    /// Last simulation along with the npc and drops it rolled.
+    pub simulation: Option<(usize, NpcData, DropSimulation)>,
+}
+
+impl NpcDropRates {
+    pub fn simulate(&mut self, id: usize, npc: &NpcData) {
+        let simulation = DropSimulation::run(
+            npc,
+            u64::from(self.kills_input.value),
+            &mut rand::thread_rng(),
+        );
+
+        self.simulation = Some((id, npc.clone(), simulation));
+    }
+
+    fn chance_rows(&self, npc: &NpcData, items: &[ItemData]) -> Vec<ActionRow<Message>> {
+        let total = npc.total_shares();
+        let mut rows = Vec::new();
+
+        for chance in npc.drop_chances() {
+            let drops: Vec<_> = npc.drops[chance.slot]
+                .items
+                .iter()
+                .filter(|item| item.amount > 0)
+                .map(|item| format!("{} x{}", item_label(items, item.item), item.amount))
+                .collect();
+
+            rows.push(ActionRow::new(format!(
+                "Slot {}: {:.2}% ({} of {} shares) {}",
+                chance.slot,
+                chance.chance * 100.0,
+                chance.shares,
+                total,
+                drops.join(", ")
+            )));
+        }
+
+        rows.push(ActionRow::new(format!(
+            "Nothing: {:.2}% ({} free shares)",
+            npc.nothing_chance() * 100.0,
+            npc.free_shares
+        )));
+
+        let expected = npc.expected_drops();
+
+        for (item, amount) in &expected {
+            rows.push(ActionRow::new(format!(
+                "{}: {:.3} per kill",
+                item_label(items, *item),
+                amount
+            )));
+        }
+
+        rows.push(ActionRow::new(format!(
+            "Expected value: {:.2} gold per kill",
+            drop_value(expected, items)
+        )));
+
+        rows
+    }
+
+    fn simulation_rows(
+        &self,
+        id: usize,
+        npc: &NpcData,
+        items: &[ItemData],
+    ) -> Vec<ActionRow<Message>> {
+        let Some((sim_id, sim_npc, simulation)) = &self.simulation else {
+            return Vec::new();
+        };
+
+        if *sim_id != id || sim_npc.drops != npc.drops || sim_npc.free_shares != npc.free_shares {
+            return vec![ActionRow::new("The drops changed, simulate again.")];
+        }
+
+        let mut rows = vec![ActionRow::new(format!(
+            "Simulated {} kills, nothing dropped {} times ({:.2}%)",
+            simulation.kills,
+            simulation.nothing(),
+            simulation.per_kill(simulation.nothing()) * 100.0
+        ))];
+
+        for (slot, hits) in simulation.slots.iter().enumerate() {
+            if *hits > 0 {
+                rows.push(ActionRow::new(format!(
+                    "Slot {} rolled {} times ({:.2}%)",
+                    slot,
+                    hits,
+                    simulation.per_kill(*hits) * 100.0
+                )));
+            }
+        }
+
+        for (item, amount) in &simulation.items {
+            rows.push(ActionRow::new(format!(
+                "{}: {} dropped, {:.3} per kill",
+                item_label(items, *item),
+                amount,
+                simulation.per_kill(*amount)
+            )));
+        }
+
+        let value = drop_value(
+            simulation
+                .items
+                .iter()
+                .map(|(item, amount)| (*item, *amount as f64)),
+            items,
+        );
+
+        rows.push(ActionRow::new(format!(
+            "Value: {:.0} gold, {:.2} per kill",
+            value,
+            if simulation.kills == 0 {
+                0.0
+            } else {
+                value / simulation.kills as f64
+            }
+        )));
+
+        rows
+    }
+
+    pub fn layout(
+        &self,
+        id: usize,
+        npc: &NpcData,
+        items: &[ItemData],
+    ) -> Option<Element<'_, Message>> {
+        if !self.show {
+            return None;
+        }
+
+        let mut rows = self.chance_rows(npc, items);
+        rows.extend(self.simulation_rows(id, npc, items));
+
+        Some(
+            column![
+                action_list(
+                    &format!("Drop Rates of Npc {}", id),
+                    rows,
+                    Message::CloseDropRates,
+                ),
+                row![
+                    text("Kills:"),
+                    self.kills_input
+                        .view(0, 1, 1_000_000, 100, Message::DropKillsInput, None),
+                    button("Simulate").on_press(Message::SimulateDrops),
+                ]
+                .spacing(6)
+                .align_y(Alignment::Center),
+            ]
+            .spacing(6)
+            .into(),
+        )
+    }
+}
+
+fn item_label(items: &[ItemData], item: u32) -> String {
+    match items.get(item as usize) {
+        Some(data) => format!("Item {} ({})", item, data.name),
+        None => format!("Item {} (missing)", item),
+    }
+}
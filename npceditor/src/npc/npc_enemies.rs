@@ -1,6 +1,7 @@
 use crate::npc::*;
 use ascending_ui::*;
 use iced::{
+    alignment::Alignment,
     widget::{button, column, row, text, PickList, Rule},
     Element, Length,
 };
@@ -15,7 +16,9 @@ pub struct NpcEnemies {
 }
 
 impl NpcEnemies {
-    pub fn layout(&self) -> Element<Message> {
+    /// `npc_name` is the name of the npc at the index input, None when it
+    /// does not exist.
+    pub fn layout(&self, npc_name: Option<&str>) -> Element<'_, Message> {
         column![
             row![
                 Rule::horizontal(0),
@@ -32,11 +35,14 @@ impl NpcEnemies {
                 text("Npc Index:"),
                 self.npc_index_input
                     .view(21, 0, u32::MAX, 1, Message::GenericU32Input, None),
+                button("Pick").on_press(Message::EnemyPicker(PickerMessage::Open(0))),
+                text(npc_name.unwrap_or("Missing npc").to_owned()),
                 button("Update").on_press(Message::UpdateEnemy),
                 button("Add").on_press(Message::AddEnemy),
                 button("Remove").on_press(Message::RemoveEnemy),
             ]
-            .spacing(15),
+            .spacing(15)
+            .align_y(Alignment::Center),
         ]
         .width(Length::Fill)
         .spacing(12)
@@ -45,46 +51,46 @@ impl NpcEnemies {
 }
 
 pub fn add_enemy_data(npc_ui: &mut NpcUI, npc_index: u32) {
-    if npc_ui.data[npc_ui.currentid].0.enemies.is_empty() {
+    if npc_ui.core.data[npc_ui.core.currentid].0.enemies.is_empty() {
         npc_ui.enemies.enemy_list.clear();
     }
 
-    let list_count = npc_ui.data[npc_ui.currentid].0.enemies.len();
+    let list_count = npc_ui.core.data[npc_ui.core.currentid].0.enemies.len();
 
     npc_ui.enemies.enemy_list.push(ListData::new(
         list_count,
-        npc_ui.data[npc_index as usize].0.name.clone(),
+        npc_ui.core.data[npc_index as usize].0.name.clone(),
     ));
     npc_ui.enemies.enemydrop_select = Some(npc_ui.enemies.enemy_list[list_count].clone());
-    npc_ui.data[npc_ui.currentid]
+    npc_ui.core.data[npc_ui.core.currentid]
         .0
         .enemies
         .push(npc_index as u64);
 }
 
 pub fn remove_enemy_data(npc_ui: &mut NpcUI) {
-    if npc_ui.data[npc_ui.currentid].0.enemies.is_empty() {
+    if npc_ui.core.data[npc_ui.core.currentid].0.enemies.is_empty() {
         return;
     }
 
-    npc_ui.data[npc_ui.currentid]
+    npc_ui.core.data[npc_ui.core.currentid]
         .0
         .enemies
         .remove(npc_ui.currentenemyslot);
 
-    new_enemies_data(npc_ui, npc_ui.currentid);
+    new_enemies_data(npc_ui, npc_ui.core.currentid);
 }
 
 pub fn update_enemy_data(npc_ui: &mut NpcUI, npc_index: u32) {
-    let got_data = !npc_ui.data[npc_ui.currentid].0.enemies.is_empty();
+    let got_data = !npc_ui.core.data[npc_ui.core.currentid].0.enemies.is_empty();
 
     if !got_data {
         npc_ui.enemies.enemy_list.clear();
         npc_ui.enemies.enemy_list.push(ListData::new(
             0,
-            npc_ui.data[npc_index as usize].0.name.clone(),
+            npc_ui.core.data[npc_index as usize].0.name.clone(),
         ));
-        npc_ui.data[npc_ui.currentid]
+        npc_ui.core.data[npc_ui.core.currentid]
             .0
             .enemies
             .push(npc_index as u64);
@@ -92,8 +98,9 @@ pub fn update_enemy_data(npc_ui: &mut NpcUI, npc_index: u32) {
     } else {
         npc_ui.enemies.enemy_list[npc_ui.currentenemyslot]
             .name
-            .clone_from(&npc_ui.data[npc_index as usize].0.name);
-        npc_ui.data[npc_ui.currentid].0.enemies[npc_ui.currentenemyslot] = npc_index as u64;
+            .clone_from(&npc_ui.core.data[npc_index as usize].0.name);
+        npc_ui.core.data[npc_ui.core.currentid].0.enemies[npc_ui.currentenemyslot] =
+            npc_index as u64;
     }
 
     npc_ui.enemies.enemydrop_select =
@@ -101,7 +108,7 @@ pub fn update_enemy_data(npc_ui: &mut NpcUI, npc_index: u32) {
 }
 
 pub fn new_enemies_data(npc_ui: &mut NpcUI, index: usize) {
-    let got_data = !npc_ui.data[index].0.enemies.is_empty();
+    let got_data = !npc_ui.core.data[index].0.enemies.is_empty();
 
     npc_ui.enemies.enemy_list.clear();
 
@@ -111,18 +118,18 @@ pub fn new_enemies_data(npc_ui: &mut NpcUI, index: usize) {
             .enemy_list
             .push(ListData::new(0, String::new()));
     } else {
-        for (i, &npcindex) in npc_ui.data[index].0.enemies.iter().enumerate() {
+        for (i, &npcindex) in npc_ui.core.data[index].0.enemies.iter().enumerate() {
             npc_ui.enemies.enemy_list.push(ListData::new(
                 i,
-                npc_ui.data[npcindex as usize].0.name.clone(),
+                npc_ui.core.data[npcindex as usize].0.name.clone(),
             ));
         }
-        npc_ui.enemies.npc_index_input.value = npc_ui.data[index].0.enemies[0] as u32;
+        npc_ui.enemies.npc_index_input.value = npc_ui.core.data[index].0.enemies[0] as u32;
     }
     npc_ui.enemies.enemydrop_select = Some(npc_ui.enemies.enemy_list[0].clone());
 }
 
 pub fn select_list(npc_ui: &mut NpcUI) {
     npc_ui.enemies.npc_index_input.value =
-        npc_ui.data[npc_ui.currentid].0.enemies[npc_ui.currentenemyslot] as u32;
+        npc_ui.core.data[npc_ui.core.currentid].0.enemies[npc_ui.currentenemyslot] as u32;
 }
@@ -1,330 +1,366 @@
-use crate::{load_config, npc::*, ConfigData};
+use crate::{npc::*, CONFIG_FILE};
+use ascending_data::*;
 use ascending_types::*;
 use ascending_ui::*;
 use iced::{
-    widget::{column, scrollable, Container},
-    Element, Length,
+    widget::{button, column, row, scrollable, Column, Container},
+    Element, Length, Task,
 };
+use std::sync::{Arc, Mutex};
 
-#[allow(dead_code)]
-#[derive(Educe)]
-#[educe(Default)]
 pub struct NpcUI {
-    #[educe(Default(expression = Vec::with_capacity(MAX_NPCS)))]
-    pub data: Vec<(NpcData, bool)>,
+    pub core: EditorCore<NpcData>,
     menu: NpcUIMenu,
     generic: NpcUIGenerics,
     settings: NpcUISettings,
     pub enemies: NpcEnemies,
-    pub currentid: usize,
-    pub currentitemdropslot: usize,
+    drop_grid: NpcDropGrid,
     pub currentenemyslot: usize,
-    config: ConfigData,
+    drop_rates: NpcDropRates,
+    /// Items of the project, for the names, icons and prices of drops.
+    items: Vec<ItemData>,
+    item_picker: RecordPicker,
+    enemy_picker: RecordPicker,
+    sprite_gallery: SpriteGallery,
 }
 
 impl UiRenderer for NpcUI {
     type Message = Message;
 
-    fn update(&mut self, msg: Message) {
+    fn update(&mut self, msg: Message) -> Task<Message> {
+        let task = self.handle_message(msg);
+        let filters = &self.menu.filters;
+
+        self.core.refresh_records(|data| filters.passes(data));
+        self.core.track_unsaved();
+        task
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        self.layout()
+    }
+}
+
+impl NpcUI {
+    fn handle_message(&mut self, msg: Message) -> Task<Message> {
+        let id = self.core.currentid;
+        let before = self.core.data[id].0.clone();
+        let merge = matches!(msg, Message::NameInput(_));
+
         match msg {
-            Message::SaveAllButtonPress => {
-                self.save_all();
-                return;
+            Message::Editor(msg) => {
+                let (drop_rates, items, item_picker) =
+                    (&mut self.drop_rates, &mut self.items, &mut self.item_picker);
+                let task = self.core.update(msg, |config, _| {
+                    drop_rates.show = false;
+                    load_items(items, item_picker, config);
+                    Ok(())
+                });
+
+                if self.core.take_refresh() {
+                    self.set_object_to_layout(self.core.currentid);
+                }
+
+                return task.map(Message::Editor);
+            }
+            Message::ShowSpriteGallery => {
+                self.sprite_gallery.open(
+                    &self.core.config.project.resources,
+                    self.core
+                        .data
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, (npc, _))| *i != id && !npc.name.is_empty())
+                        .map(|(_, (npc, _))| i64::from(npc.sprite)),
+                );
+                return Task::none();
+            }
+            Message::SpriteGallery(msg) => {
+                let Some(sprite) = self
+                    .sprite_gallery
+                    .update(msg)
+                    .and_then(|sprite| i32::try_from(sprite).ok())
+                else {
+                    return Task::none();
+                };
+
+                self.generic.sprite_input.value = sprite;
+                self.core.current_mut().sprite = sprite;
+            }
+            Message::ItemPicker(PickerMessage::List(RecordListMessage::Select(item))) => {
+                let Some(id) = self.item_picker.pick() else {
+                    return Task::none();
+                };
+                let (slot, index) = (id / 5, id % 5);
+
+                self.drop_grid.items[slot][index].item_id.value = item as u32;
+                self.core.current_mut().drops[slot].items[index].item = item as u32;
             }
-            Message::SaveButtonPress => {
-                if self.config.save_json {
-                    self.data[self.currentid]
-                        .0
-                        .save_file(self.currentid)
-                        .unwrap();
-                }
-                self.data[self.currentid]
-                    .0
-                    .save_bin_file(self.currentid)
-                    .unwrap();
-                return;
+            Message::ItemPicker(msg) => {
+                return self.item_picker.update(msg).map(Message::ItemPicker);
             }
-            Message::RevertButtonPress => {
-                let item = NpcData::load_file(self.currentid).unwrap();
-                self.data[self.currentid].0 = item.0;
-                self.data[self.currentid].1 = false;
-                self.set_object_to_layout(self.currentid);
-                return;
+            Message::EnemyPicker(PickerMessage::List(RecordListMessage::Select(npc))) => {
+                if self.enemy_picker.pick().is_some() {
+                    self.enemies.npc_index_input.value = npc as u32;
+                }
+                return Task::none();
+            }
+            Message::EnemyPicker(msg) => {
+                if matches!(msg, PickerMessage::Open(_)) {
+                    self.enemy_picker.set_records(
+                        &self.core.config.project.resources,
+                        self.core
+                            .data
+                            .iter()
+                            .map(|(npc, _)| (npc.name.clone(), i64::from(npc.sprite))),
+                    );
+                }
+
+                return self.enemy_picker.update(msg).map(Message::EnemyPicker);
+            }
+            Message::ShowDropRates => {
+                self.drop_rates.show = true;
+                load_items(&mut self.items, &mut self.item_picker, &self.core.config);
+                self.drop_rates.simulation = None;
+                return Task::none();
+            }
+            Message::CloseDropRates => {
+                self.drop_rates.show = false;
+                return Task::none();
+            }
+            Message::DropKillsInput((_, data)) => {
+                self.drop_rates.kills_input.value = data.get_data();
+                return Task::none();
+            }
+            Message::SimulateDrops => {
+                self.drop_rates.simulate(id, self.core.current());
+                return Task::none();
+            }
+            Message::FilterBehaviour(behaviour) => {
+                self.menu.filters.behaviour = behaviour;
+                self.core.filters_changed();
+                return Task::none();
             }
-            Message::ListSelect(data) => {
-                self.currentid = data.id;
-                self.menu.list_selected = Some(data);
-                self.set_object_to_layout(self.currentid);
-                return;
+            Message::FilterLevel((id, input)) => {
+                self.menu.filters.set_level(id, &input);
+                self.core.filters_changed();
+                return Task::none();
+            }
+            Message::ClearFilters => {
+                self.menu.filters = NpcFilters::default();
+                self.core.filters_changed();
+                return Task::none();
             }
             Message::BehaviourTypeSelect(data) => {
                 self.generic.behaviour_selected = Some(data);
-                self.data[self.currentid].0.behaviour = data;
+                self.core.current_mut().behaviour = data;
             }
             Message::GenericBoolInput((id, data)) => match id {
                 0 => {
                     self.settings.target_auto_switch = data.get_data();
-                    self.data[self.currentid].0.target_auto_switch = data.get_data();
+                    self.core.current_mut().target_auto_switch = data.get_data();
                 }
                 1 => {
                     self.settings.target_attacked_switch = data.get_data();
-                    self.data[self.currentid].0.target_attacked_switch = data.get_data();
+                    self.core.current_mut().target_attacked_switch = data.get_data();
                 }
                 2 => {
                     self.settings.target_range_dropout = data.get_data();
-                    self.data[self.currentid].0.target_range_dropout = data.get_data();
+                    self.core.current_mut().target_range_dropout = data.get_data();
                 }
                 3 => {
                     self.settings.can_target = data.get_data();
-                    self.data[self.currentid].0.can_target = data.get_data();
+                    self.core.current_mut().can_target = data.get_data();
                 }
                 4 => {
                     self.settings.can_move = data.get_data();
-                    self.data[self.currentid].0.can_move = data.get_data();
+                    self.core.current_mut().can_move = data.get_data();
                 }
                 5 => {
                     self.settings.can_attack_player = data.get_data();
-                    self.data[self.currentid].0.can_attack_player = data.get_data();
+                    self.core.current_mut().can_attack_player = data.get_data();
                 }
                 6 => {
                     self.settings.has_selfonly = data.get_data();
-                    self.data[self.currentid].0.has_selfonly = data.get_data();
+                    self.core.current_mut().has_selfonly = data.get_data();
                 }
                 7 => {
                     self.settings.has_friendonly = data.get_data();
-                    self.data[self.currentid].0.has_friendonly = data.get_data();
+                    self.core.current_mut().has_friendonly = data.get_data();
                 }
                 8 => {
                     self.settings.has_groundonly = data.get_data();
-                    self.data[self.currentid].0.has_groundonly = data.get_data();
+                    self.core.current_mut().has_groundonly = data.get_data();
                 }
                 9 => {
                     self.settings.has_allys = data.get_data();
-                    self.data[self.currentid].0.has_allys = data.get_data();
+                    self.core.current_mut().has_allys = data.get_data();
                 }
                 10 => {
                     self.settings.can_attack = data.get_data();
-                    self.data[self.currentid].0.can_attack = data.get_data();
+                    self.core.current_mut().can_attack = data.get_data();
                 }
                 11 => {
                     self.settings.runsaway = data.get_data();
-                    self.data[self.currentid].0.runsaway = data.get_data();
+                    self.core.current_mut().runsaway = data.get_data();
                 }
                 12 => {
                     self.settings.canpassthru = data.get_data();
-                    self.data[self.currentid].0.canpassthru = data.get_data();
+                    self.core.current_mut().canpassthru = data.get_data();
                 }
                 13 => {
                     self.settings.isanimated = data.get_data();
-                    self.data[self.currentid].0.isanimated = data.get_data();
+                    self.core.current_mut().isanimated = data.get_data();
                 }
                 14 => {
                     self.settings.has_enemies = data.get_data();
-                    self.data[self.currentid].0.has_enemies = data.get_data();
+                    self.core.current_mut().has_enemies = data.get_data();
                 }
-                _ => return,
+                _ => return Task::none(),
             },
             Message::GenericU8Input((id, data)) => match id {
                 0 => {
                     self.generic.sizex_input.value = data.get_data();
-                    self.data[self.currentid].0.size.x = data.get_data();
+                    self.core.current_mut().size.x = data.get_data();
                 }
                 1 => {
                     self.generic.sizey_input.value = data.get_data();
-                    self.data[self.currentid].0.size.y = data.get_data();
+                    self.core.current_mut().size.y = data.get_data();
                 }
                 2 => {
                     self.generic.sizeh_input.value = data.get_data();
-                    self.data[self.currentid].0.size.height = data.get_data();
+                    self.core.current_mut().size.height = data.get_data();
                 }
                 3 => {
                     self.generic.sizew_input.value = data.get_data();
-                    self.data[self.currentid].0.size.width = data.get_data();
+                    self.core.current_mut().size.width = data.get_data();
                 }
-                _ => return,
+                _ => return Task::none(),
             },
             Message::GenericI32Input((id, data)) => match id {
                 0 => {
                     self.generic.sprite_input.value = data.get_data();
-                    self.data[self.currentid].0.sprite = data.get_data();
+                    self.core.current_mut().sprite = data.get_data();
                 }
                 1 => {
                     self.generic.level_input.value = data.get_data();
-                    self.data[self.currentid].0.level = data.get_data();
+                    self.core.current_mut().level = data.get_data();
                 }
                 2 => {
                     self.generic.sight_input.value = data.get_data();
-                    self.data[self.currentid].0.sight = data.get_data();
+                    self.core.current_mut().sight = data.get_data();
                 }
                 3 => {
                     self.generic.follow_sight_input.value = data.get_data();
-                    self.data[self.currentid].0.follow_sight = data.get_data();
+                    self.core.current_mut().follow_sight = data.get_data();
                 }
                 4 => {
                     self.generic.range_input.value = data.get_data();
-                    self.data[self.currentid].0.range = data.get_data();
+                    self.core.current_mut().range = data.get_data();
                 }
-                _ => return,
+                _ => return Task::none(),
             },
             Message::GenericU32Input((id, data)) => match id {
                 0 => {
                     self.settings.run_damage.value = data.get_data();
-                    self.data[self.currentid].0.run_damage = data.get_data();
+                    self.core.current_mut().run_damage = data.get_data();
                 }
                 1 => {
                     self.generic.maxhp_input.value = data.get_data();
-                    self.data[self.currentid].0.maxhp = data.get_data();
+                    self.core.current_mut().maxhp = data.get_data();
                 }
                 2 => {
                     self.generic.maxsp_input.value = data.get_data();
-                    self.data[self.currentid].0.maxsp = data.get_data();
+                    self.core.current_mut().maxsp = data.get_data();
                 }
                 3 => {
                     self.generic.maxmp_input.value = data.get_data();
-                    self.data[self.currentid].0.maxmp = data.get_data();
+                    self.core.current_mut().maxmp = data.get_data();
                 }
                 4 => {
                     self.generic.pdamage_input.value = data.get_data();
-                    self.data[self.currentid].0.pdamage = data.get_data();
+                    self.core.current_mut().pdamage = data.get_data();
                 }
                 5 => {
                     self.generic.pdef_input.value = data.get_data();
-                    self.data[self.currentid].0.pdefense = data.get_data();
+                    self.core.current_mut().pdefense = data.get_data();
                 }
                 6 => {
                     self.generic.walkdistance_input.value = data.get_data();
-                    self.data[self.currentid].0.walkdistance = data.get_data();
+                    self.core.current_mut().walkdistance = data.get_data();
                 }
                 7 => {
                     self.generic.mindamage_input.value = data.get_data();
-                    self.data[self.currentid].0.mindamage = data.get_data();
+                    self.core.current_mut().mindamage = data.get_data();
                 }
                 8 => {
                     self.generic.maxdamage_input.value = data.get_data();
-                    self.data[self.currentid].0.maxdamage = data.get_data();
-                }
-                // Drop ID
-                9 => {
-                    self.generic.item_drops[0].item_id.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[0].item =
-                        data.get_data();
-                }
-                10 => {
-                    self.generic.item_drops[1].item_id.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[1].item =
-                        data.get_data();
-                }
-                11 => {
-                    self.generic.item_drops[2].item_id.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[2].item =
-                        data.get_data();
-                }
-                12 => {
-                    self.generic.item_drops[3].item_id.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[3].item =
-                        data.get_data();
-                }
-                13 => {
-                    self.generic.item_drops[4].item_id.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[4].item =
-                        data.get_data();
-                }
-                // Drop Amount
-                14 => {
-                    self.generic.item_drops[0].amount.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[0].amount =
-                        data.get_data();
-                }
-                15 => {
-                    self.generic.item_drops[1].amount.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[1].amount =
-                        data.get_data();
-                }
-                16 => {
-                    self.generic.item_drops[2].amount.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[2].amount =
-                        data.get_data();
-                }
-                17 => {
-                    self.generic.item_drops[3].amount.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[3].amount =
-                        data.get_data();
-                }
-                18 => {
-                    self.generic.item_drops[4].amount.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[4].amount =
-                        data.get_data();
-                }
-                // Drop Data
-                19 => {
-                    self.generic.slotshares_input.value = data.get_data();
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].shares =
-                        data.get_data();
+                    self.core.current_mut().maxdamage = data.get_data();
                 }
                 20 => {
-                    self.generic.freeshares_input.value = data.get_data();
-                    self.data[self.currentid].0.free_shares = data.get_data();
+                    self.drop_grid.free_shares.value = data.get_data();
+                    self.core.current_mut().free_shares = data.get_data();
                 }
                 // Enemy
                 21 => {
                     self.enemies.npc_index_input.value = data.get_data();
                 }
-                _ => return,
+                _ => return Task::none(),
             },
             Message::GenericI64Input((id, data)) => match id {
                 0 => {
                     self.settings.target_switch_chance.value = data.get_data();
-                    self.data[self.currentid].0.target_auto_switch_chance = data.get_data();
+                    self.core.current_mut().target_auto_switch_chance = data.get_data();
                 }
                 1 => {
                     self.generic.movement_wait_input.value = data.get_data();
-                    self.data[self.currentid].0.movement_wait = data.get_data();
+                    self.core.current_mut().movement_wait = data.get_data();
                 }
                 2 => {
                     self.generic.attack_wait_input.value = data.get_data();
-                    self.data[self.currentid].0.attack_wait = data.get_data();
+                    self.core.current_mut().attack_wait = data.get_data();
                 }
                 3 => {
                     self.generic.intervaled_wait_input.value = data.get_data();
-                    self.data[self.currentid].0.intervaled_wait = data.get_data();
+                    self.core.current_mut().intervaled_wait = data.get_data();
                 }
                 4 => {
                     self.generic.respawn_wait_input.value = data.get_data();
-                    self.data[self.currentid].0.respawn_wait = data.get_data();
+                    self.core.current_mut().respawn_wait = data.get_data();
                 }
                 5 => {
                     self.generic.spawn_wait_input.value = data.get_data();
-                    self.data[self.currentid].0.spawn_wait = data.get_data();
+                    self.core.current_mut().spawn_wait = data.get_data();
                 }
                 6 => {
                     self.generic.exp_input.value = data.get_data();
-                    self.data[self.currentid].0.exp = data.get_data();
+                    self.core.current_mut().exp = data.get_data();
                 }
-                _ => return,
+                _ => return Task::none(),
             },
             Message::ChooseTime1 => {
                 self.settings.show_time[0] = true;
-                return;
+                return Task::none();
             }
             Message::SubmitTime1(time) => {
                 use chrono::Timelike;
                 let naive: chrono::NaiveTime = time.into();
-                self.data[self.currentid].0.spawntime.0.hour = naive.hour();
-                self.data[self.currentid].0.spawntime.0.min = naive.minute();
-                self.data[self.currentid].0.spawntime.0.sec = naive.second();
-                self.settings.spawntime_data.0 = self.data[self.currentid].0.spawntime.0;
+                self.core.current_mut().spawntime.0.hour = naive.hour();
+                self.core.current_mut().spawntime.0.min = naive.minute();
+                self.core.current_mut().spawntime.0.sec = naive.second();
+                self.settings.spawntime_data.0 = self.core.current().spawntime.0;
             }
             Message::ChooseTime2 => {
                 self.settings.show_time[1] = true;
-                return;
+                return Task::none();
             }
             Message::SubmitTime2(time) => {
                 use chrono::Timelike;
                 let naive: chrono::NaiveTime = time.into();
-                self.data[self.currentid].0.spawntime.1.hour = naive.hour();
-                self.data[self.currentid].0.spawntime.1.min = naive.minute();
-                self.data[self.currentid].0.spawntime.1.sec = naive.second();
-                self.settings.spawntime_data.1 = self.data[self.currentid].0.spawntime.1;
+                self.core.current_mut().spawntime.1.hour = naive.hour();
+                self.core.current_mut().spawntime.1.min = naive.minute();
+                self.core.current_mut().spawntime.1.sec = naive.second();
+                self.settings.spawntime_data.1 = self.core.current().spawntime.1;
             }
             Message::CancelTime => {
                 self.settings.show_time[0] = false;
@@ -333,44 +369,29 @@ impl UiRenderer for NpcUI {
             Message::NameInput(value) => {
                 if value.len() < 64 {
                     self.generic.txt_value = value;
-                    self.data[self.currentid]
-                        .0
+                    self.core
+                        .current_mut()
                         .name
                         .clone_from(&self.generic.txt_value);
-                    self.menu.list[self.currentid]
-                        .name
-                        .clone_from(&self.generic.txt_value);
-                    self.menu.list_selected = Some(self.menu.list[self.currentid].clone());
                 } else {
-                    return;
+                    return Task::none();
                 }
             }
-            Message::ItemDropSlotSelect(data) => {
-                self.generic.itemdrop_selected = Some(data);
-                self.currentitemdropslot = data;
+            Message::DropSharesInput((slot, data)) => {
+                self.drop_grid.shares[slot].value = data.get_data();
+                self.core.current_mut().drops[slot].shares = data.get_data();
+            }
+            Message::DropItemInput((id, data)) => {
+                let (slot, index) = (id / 5, id % 5);
 
-                self.generic.slotshares_input.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].shares;
-                self.generic.item_drops[0].item_id.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[0].item;
-                self.generic.item_drops[0].amount.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[0].amount;
-                self.generic.item_drops[1].item_id.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[1].item;
-                self.generic.item_drops[1].amount.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[1].amount;
-                self.generic.item_drops[2].item_id.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[2].item;
-                self.generic.item_drops[2].amount.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[2].amount;
-                self.generic.item_drops[3].item_id.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[3].item;
-                self.generic.item_drops[3].amount.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[3].amount;
-                self.generic.item_drops[4].item_id.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[4].item;
-                self.generic.item_drops[4].amount.value =
-                    self.data[self.currentid].0.drops[self.currentitemdropslot].items[4].amount;
+                self.drop_grid.items[slot][index].item_id.value = data.get_data();
+                self.core.current_mut().drops[slot].items[index].item = data.get_data();
+            }
+            Message::DropAmountInput((id, data)) => {
+                let (slot, index) = (id / 5, id % 5);
+
+                self.drop_grid.items[slot][index].amount.value = data.get_data();
+                self.core.current_mut().drops[slot].items[index].amount = data.get_data();
             }
             Message::EnemyListSelect(data) => {
                 self.currentenemyslot = data.id;
@@ -382,158 +403,192 @@ impl UiRenderer for NpcUI {
             Message::RemoveEnemy => remove_enemy_data(self),
         }
 
-        self.data[self.currentid].1 = true;
-    }
+        self.core.edited(id, before, merge);
 
-    fn view(&self) -> Element<Message> {
-        self.layout()
+        Task::none()
     }
-}
-
-impl NpcUI {
-    pub fn new() -> Self {
-        let config: ConfigData = load_config();
 
+    /// Opens the project of the config, failing if its records can not be read.
+    pub fn new(
+        config: EditorConfig,
+        journal: Arc<Mutex<Journal<NpcData>>>,
+    ) -> Result<Self, String> {
+        let tools = EditorTools {
+            sprites: true,
+            referenced: true,
+        };
         let mut ui = NpcUI {
-            data: NpcData::load_files(config.save_json).unwrap(),
-            config,
-            ..Default::default()
+            core: EditorCore::new(config, CONFIG_FILE, journal, tools),
+            menu: NpcUIMenu::default(),
+            generic: NpcUIGenerics::default(),
+            settings: NpcUISettings::default(),
+            enemies: NpcEnemies::default(),
+            drop_grid: NpcDropGrid::default(),
+            currentenemyslot: 0,
+            drop_rates: NpcDropRates::default(),
+            items: Vec::new(),
+            item_picker: RecordPicker::default(),
+            enemy_picker: RecordPicker::new(PickerKind::Npc),
+            sprite_gallery: SpriteGallery::new(PickerKind::Npc),
         };
 
-        for (i, v) in ui.data.iter().enumerate() {
-            ui.menu.list.push(ListData::new(i, v.0.name.clone()));
-        }
-
         for i in 0..=AIBehavior::ReactiveHealer as usize {
             ui.generic.behaviours.push(AIBehavior::from_index(i))
         }
 
-        ui.menu.list_selected = Some(ui.menu.list[0].clone());
+        let (items, item_picker) = (&mut ui.items, &mut ui.item_picker);
+        ui.core.load_project(|config, _| {
+            load_items(items, item_picker, config);
+            Ok(())
+        })?;
         ui.set_object_to_layout(0);
 
-        {
-            new_enemies_data(&mut ui, 0);
-        }
-
-        ui
-    }
-
-    pub fn save_all(&mut self) {
-        for (i, v) in self.data.iter().enumerate() {
-            if !v.1 {
-                continue;
-            }
-
-            if self.config.save_json {
-                if let Err(e) = v.0.save_file(i) {
-                    println!("Could not save NPC {}, err {}", i, e);
-                }
-            }
-            if let Err(e) = v.0.save_bin_file(i) {
-                println!("Could not save bin NPC {}, err {}", i, e);
-            }
-        }
+        let filters = &ui.menu.filters;
+        ui.core.refresh_records(|data| filters.passes(data));
+        Ok(ui)
     }
 
     fn set_object_to_layout(&mut self, index: usize) {
-        self.generic.txt_value.clone_from(&self.data[index].0.name);
-        self.generic.sprite_input.value = self.data[index].0.sprite;
-        self.generic.behaviour_selected = Some(self.data[index].0.behaviour);
-        self.generic.sizex_input.value = self.data[index].0.size.x;
-        self.generic.sizey_input.value = self.data[index].0.size.y;
-        self.generic.sizeh_input.value = self.data[index].0.size.height;
-        self.generic.sizew_input.value = self.data[index].0.size.width;
-        self.generic.sprite_input.value = self.data[index].0.sprite;
-        self.generic.level_input.value = self.data[index].0.level;
-        self.generic.sight_input.value = self.data[index].0.sight;
-        self.generic.follow_sight_input.value = self.data[index].0.follow_sight;
-        self.generic.maxhp_input.value = self.data[index].0.maxhp;
-        self.generic.maxsp_input.value = self.data[index].0.maxsp;
-        self.generic.maxmp_input.value = self.data[index].0.maxmp;
-        self.generic.walkdistance_input.value = self.data[index].0.walkdistance;
-        self.generic.pdamage_input.value = self.data[index].0.pdamage;
-        self.generic.pdef_input.value = self.data[index].0.pdefense;
-        self.generic.maxdamage_input.value = self.data[index].0.maxdamage;
-        self.generic.mindamage_input.value = self.data[index].0.mindamage;
-        self.generic.respawn_wait_input.value = self.data[index].0.respawn_wait;
-        self.generic.movement_wait_input.value = self.data[index].0.movement_wait;
-        self.generic.attack_wait_input.value = self.data[index].0.attack_wait;
-        self.generic.intervaled_wait_input.value = self.data[index].0.intervaled_wait;
-        self.generic.spawn_wait_input.value = self.data[index].0.spawn_wait;
-        self.generic.range_input.value = self.data[index].0.range;
-        self.generic.exp_input.value = self.data[index].0.exp;
+        let data = &self.core.data[index].0;
 
-        self.settings.target_auto_switch = self.data[index].0.target_auto_switch;
-        self.settings.target_attacked_switch = self.data[index].0.target_attacked_switch;
-        self.settings.target_range_dropout = self.data[index].0.target_range_dropout;
-        self.settings.can_target = self.data[index].0.can_target;
-        self.settings.can_move = self.data[index].0.can_move;
-        self.settings.can_attack_player = self.data[index].0.can_attack_player;
-        self.settings.has_selfonly = self.data[index].0.has_selfonly;
-        self.settings.has_friendonly = self.data[index].0.has_friendonly;
-        self.settings.has_groundonly = self.data[index].0.has_groundonly;
-        self.settings.has_allys = self.data[index].0.has_allys;
-        self.settings.has_enemies = self.data[index].0.has_enemies;
-        self.settings.can_attack = self.data[index].0.can_attack;
-        self.settings.runsaway = self.data[index].0.runsaway;
-        self.settings.canpassthru = self.data[index].0.canpassthru;
-        self.settings.isanimated = self.data[index].0.isanimated;
-        self.settings.target_switch_chance.value = self.data[index].0.target_auto_switch_chance;
-        self.settings.run_damage.value = self.data[index].0.run_damage;
-        self.settings.spawntime_data.0 = self.data[index].0.spawntime.0;
-        self.settings.spawntime_data.1 = self.data[index].0.spawntime.1;
+        self.generic.txt_value.clone_from(&data.name);
+        self.generic.sprite_input.value = data.sprite;
+        self.generic.behaviour_selected = Some(data.behaviour);
+        self.generic.sizex_input.value = data.size.x;
+        self.generic.sizey_input.value = data.size.y;
+        self.generic.sizeh_input.value = data.size.height;
+        self.generic.sizew_input.value = data.size.width;
+        self.generic.sprite_input.value = data.sprite;
+        self.generic.level_input.value = data.level;
+        self.generic.sight_input.value = data.sight;
+        self.generic.follow_sight_input.value = data.follow_sight;
+        self.generic.maxhp_input.value = data.maxhp;
+        self.generic.maxsp_input.value = data.maxsp;
+        self.generic.maxmp_input.value = data.maxmp;
+        self.generic.walkdistance_input.value = data.walkdistance;
+        self.generic.pdamage_input.value = data.pdamage;
+        self.generic.pdef_input.value = data.pdefense;
+        self.generic.maxdamage_input.value = data.maxdamage;
+        self.generic.mindamage_input.value = data.mindamage;
+        self.generic.respawn_wait_input.value = data.respawn_wait;
+        self.generic.movement_wait_input.value = data.movement_wait;
+        self.generic.attack_wait_input.value = data.attack_wait;
+        self.generic.intervaled_wait_input.value = data.intervaled_wait;
+        self.generic.spawn_wait_input.value = data.spawn_wait;
+        self.generic.range_input.value = data.range;
+        self.generic.exp_input.value = data.exp;
 
-        self.generic.itemdrop_selected = Some(0);
-        self.currentitemdropslot = 0;
+        self.settings.target_auto_switch = data.target_auto_switch;
+        self.settings.target_attacked_switch = data.target_attacked_switch;
+        self.settings.target_range_dropout = data.target_range_dropout;
+        self.settings.can_target = data.can_target;
+        self.settings.can_move = data.can_move;
+        self.settings.can_attack_player = data.can_attack_player;
+        self.settings.has_selfonly = data.has_selfonly;
+        self.settings.has_friendonly = data.has_friendonly;
+        self.settings.has_groundonly = data.has_groundonly;
+        self.settings.has_allys = data.has_allys;
+        self.settings.has_enemies = data.has_enemies;
+        self.settings.can_attack = data.can_attack;
+        self.settings.runsaway = data.runsaway;
+        self.settings.canpassthru = data.canpassthru;
+        self.settings.isanimated = data.isanimated;
+        self.settings.target_switch_chance.value = data.target_auto_switch_chance;
+        self.settings.run_damage.value = data.run_damage;
+        self.settings.spawntime_data.0 = data.spawntime.0;
+        self.settings.spawntime_data.1 = data.spawntime.1;
 
-        self.generic.freeshares_input.value = self.data[index].0.free_shares;
-        self.generic.slotshares_input.value =
-            self.data[index].0.drops[self.currentitemdropslot].shares;
-
-        self.generic.item_drops[0].item_id.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[0].item;
-        self.generic.item_drops[0].amount.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[0].amount;
-        self.generic.item_drops[1].item_id.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[1].item;
-        self.generic.item_drops[1].amount.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[1].amount;
-        self.generic.item_drops[2].item_id.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[2].item;
-        self.generic.item_drops[2].amount.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[2].amount;
-        self.generic.item_drops[3].item_id.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[3].item;
-        self.generic.item_drops[3].amount.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[3].amount;
-        self.generic.item_drops[4].item_id.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[4].item;
-        self.generic.item_drops[4].amount.value =
-            self.data[index].0.drops[self.currentitemdropslot].items[4].amount;
+        self.drop_grid.set_drops(data);
 
         new_enemies_data(self, index);
     }
 
-    fn layout(&self) -> Element<Message> {
+    fn item_picker_layout(&self) -> Option<Element<'_, Message>> {
+        let id = self.item_picker.target()?;
+        let item = self.core.current().drops[id / 5].items[id % 5].item;
+
+        self.item_picker
+            .view(item as usize)
+            .map(|picker| picker.map(Message::ItemPicker))
+    }
+
+    fn layout(&self) -> Element<'_, Message> {
         Container::new(
-            column![
-                self.menu.layout(),
-                scrollable(column![
-                    Container::new(self.generic.layout())
-                        .padding(5)
-                        .width(Length::Fill)
-                        .center_x(Length::Fill),
-                    Container::new(self.settings.layout(&self.data[self.currentid].0))
-                        .padding(5)
-                        .width(Length::Fill)
-                        .center_x(Length::Fill),
-                    Container::new(self.enemies.layout())
-                        .padding(5)
-                        .width(Length::Fill)
-                        .center_x(Length::Fill),
-                ])
-            ]
-            .spacing(20),
+            Column::new()
+                .push_maybe(
+                    self.core
+                        .notices_layout()
+                        .map(|view| view.map(Message::Editor)),
+                )
+                .push(
+                    row![
+                        self.core.menu_layout().map(Message::Editor),
+                        button("Drop Rates").on_press(Message::ShowDropRates),
+                    ]
+                    .spacing(5),
+                )
+                .push_maybe(
+                    self.core
+                        .panels_layout()
+                        .map(|view| view.map(Message::Editor)),
+                )
+                .push_maybe(self.drop_rates.layout(
+                    self.core.currentid,
+                    self.core.current(),
+                    &self.items,
+                ))
+                .push_maybe(self.item_picker_layout())
+                .push_maybe(
+                    self.enemy_picker
+                        .view(self.enemies.npc_index_input.value as usize)
+                        .map(|picker| picker.map(Message::EnemyPicker)),
+                )
+                .push_maybe(
+                    self.sprite_gallery
+                        .view(i64::from(self.core.current().sprite))
+                        .map(|gallery| gallery.map(Message::SpriteGallery)),
+                )
+                .push(
+                    row![
+                        self.menu.list_layout(
+                            self.core.list_layout().map(Message::Editor),
+                            &self.generic.behaviours
+                        ),
+                        scrollable(column![
+                            Container::new(
+                                self.generic.layout(&self.core.config.project.resources)
+                            )
+                            .padding(5)
+                            .width(Length::Fill)
+                            .center_x(Length::Fill),
+                            Container::new(self.drop_grid.layout(
+                                self.core.current(),
+                                &self.items,
+                                &self.core.config.project.resources,
+                            ))
+                            .padding(5)
+                            .width(Length::Fill)
+                            .center_x(Length::Fill),
+                            Container::new(self.settings.layout(self.core.current()))
+                                .padding(5)
+                                .width(Length::Fill)
+                                .center_x(Length::Fill),
+                            Container::new(
+                                self.enemies.layout(
+                                    self.core
+                                        .data
+                                        .get(self.enemies.npc_index_input.value as usize)
+                                        .map(|(npc, _)| npc.name.as_str()),
+                                )
+                            )
+                            .padding(5)
+                            .width(Length::Fill)
+                            .center_x(Length::Fill),
+                        ])
+                    ]
+                    .spacing(10),
+                )
+                .spacing(20),
         )
         .width(Length::Fill)
         .height(Length::Fill)
@@ -541,3 +596,14 @@ impl NpcUI {
         .into()
     }
 }
+
+/// Reads the items again, they are edited within the item editor.
+fn load_items(items: &mut Vec<ItemData>, item_picker: &mut RecordPicker, config: &EditorConfig) {
+    *items = DataSet::load_all(&config.project.data, config.source);
+    item_picker.set_records(
+        &config.project.resources,
+        items
+            .iter()
+            .map(|item| (item.name.clone(), i64::from(item.sprite))),
+    );
+}
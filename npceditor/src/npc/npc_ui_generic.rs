@@ -1,11 +1,12 @@
 use std::path::Path;
 
 use crate::npc::*;
+use ascending_data::SpriteFolder;
 use ascending_types::*;
 use ascending_ui::*;
 use iced::{
     alignment::Alignment,
-    widget::{column, container, row, text, text_input, Container, Image, PickList, Rule},
+    widget::{button, column, container, row, text, text_input, Container, Image, PickList, Rule},
     Element, Length,
 };
 
@@ -67,26 +68,11 @@ pub struct NpcUIGenerics {
     pub exp_input: NumInput<i64, Message>,
     pub behaviours: Vec<AIBehavior>,
     pub behaviour_selected: Option<AIBehavior>,
-    pub item_drops: [Items; 5],
-    pub itemdrop_selected: Option<usize>,
-    #[educe(Default(expression = NumInput::new(0)))]
-    pub slotshares_input: NumInput<u32, Message>,
-    #[educe(Default(expression = NumInput::new(0)))]
-    pub freeshares_input: NumInput<u32, Message>,
-}
-
-#[derive(Educe)]
-#[educe(Default)]
-pub struct Items {
-    #[educe(Default(expression = NumInput::new(0)))]
-    pub item_id: NumInput<u32, Message>,
-    #[educe(Default(expression = NumInput::new(0)))]
-    pub amount: NumInput<u32, Message>,
 }
 
 impl NpcUIGenerics {
-    pub fn layout(&self) -> Element<Message> {
-        let image_path = format!("./resources/npc/p{}.png", self.sprite_input.value);
+    pub fn layout(&self, resources: &Path) -> Element<'_, Message> {
+        let image_path = SpriteFolder::NPCS.path(resources, i64::from(self.sprite_input.value));
         column![
             row![
                 Rule::horizontal(0),
@@ -104,6 +90,7 @@ impl NpcUIGenerics {
                 text("Sprite:"),
                 self.sprite_input
                     .view(0, 0, 1000, 1, Message::GenericI32Input, None),
+                button("Gallery").on_press(Message::ShowSpriteGallery),
                 text("Behaviour:"),
                 PickList::new(
                     &self.behaviours[..],
@@ -115,9 +102,9 @@ impl NpcUIGenerics {
             .spacing(15),
             row![column![
                 "NPC Sprite",
-                row![if Path::new(&image_path).exists() {
+                row![if image_path.exists() {
                     container(
-                        Image::new(&image_path)
+                        Image::new(image_path)
                             .width(Length::Fixed(32.0))
                             .height(Length::Fixed(32.0))
                             .content_fit(iced::ContentFit::None),
@@ -246,146 +233,6 @@ impl NpcUIGenerics {
                     .view(3, 1, u8::MAX, 1, Message::GenericU8Input, None),
             ]
             .spacing(15),
-            row![
-                Rule::horizontal(0),
-                text("Item Drops:"),
-                Rule::horizontal(0),
-            ]
-            .spacing(10)
-            .align_y(Alignment::Center),
-            row![
-                column![
-                    text("Drop Item Slot:"),
-                    PickList::new(
-                        vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
-                        self.itemdrop_selected,
-                        Message::ItemDropSlotSelect,
-                    ),
-                    text("Free Shares:"),
-                    self.freeshares_input
-                        .view(20, 1, u32::MAX, 1, Message::GenericU32Input, None),
-                ]
-                .spacing(10),
-                column![
-                    row![
-                        text("Shares:"),
-                        self.slotshares_input.view(
-                            19,
-                            1,
-                            u32::MAX,
-                            1,
-                            Message::GenericU32Input,
-                            None
-                        ),
-                    ]
-                    .spacing(10),
-                    row![
-                        column![
-                            text("Item ID 1:"),
-                            self.item_drops[0].item_id.view(
-                                9,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                            text("Amount:"),
-                            self.item_drops[0].amount.view(
-                                14,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                        ],
-                        column![
-                            text("Item ID 2:"),
-                            self.item_drops[1].item_id.view(
-                                10,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                            text("Amount:"),
-                            self.item_drops[1].amount.view(
-                                15,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                        ],
-                        column![
-                            text("Item ID 3:"),
-                            self.item_drops[2].item_id.view(
-                                11,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                            text("Amount:"),
-                            self.item_drops[2].amount.view(
-                                16,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                        ],
-                        column![
-                            text("Item ID 4:"),
-                            self.item_drops[3].item_id.view(
-                                12,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                            text("Amount:"),
-                            self.item_drops[3].amount.view(
-                                17,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                        ],
-                        column![
-                            text("Item ID 5:"),
-                            self.item_drops[4].item_id.view(
-                                13,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                            text("Amount:"),
-                            self.item_drops[4].amount.view(
-                                18,
-                                1,
-                                u32::MAX,
-                                1,
-                                Message::GenericU32Input,
-                                None
-                            ),
-                        ],
-                    ]
-                    .spacing(5),
-                ]
-                .spacing(5),
-            ]
-            .spacing(25)
         ]
         .spacing(10)
         .align_x(Alignment::Center)
@@ -1,34 +1,83 @@
 use crate::npc::*;
+use ascending_data::*;
 use ascending_types::*;
 use ascending_ui::*;
 use iced::{
-    widget::{button, row, PickList},
+    alignment::Alignment,
+    widget::{button, column, row, text, text_input, PickList},
     Element, Length,
 };
 
-#[derive(Educe)]
-#[educe(Default)]
+/// Narrows the record list down to npcs of a behaviour and level range.
+#[derive(Default)]
+pub struct NpcFilters {
+    pub behaviour: Option<AIBehavior>,
+    /// Lowest and highest level shown, unbounded when unset.
+    pub level: [Option<i32>; 2],
+}
+
+impl NpcFilters {
+    pub fn passes(&self, data: &NpcData) -> bool {
+        self.behaviour
+            .is_none_or(|behaviour| data.behaviour == behaviour)
+            && self.level[0].is_none_or(|min| data.level >= min)
+            && self.level[1].is_none_or(|max| data.level <= max)
+    }
+
+    /// Sets a level bound from its input, clearing it when the input is empty.
+    pub fn set_level(&mut self, id: usize, input: &str) {
+        if input.is_empty() {
+            self.level[id] = None;
+        } else if let Ok(value) = input.parse() {
+            self.level[id] = Some(value);
+        }
+    }
+}
+
+#[derive(Default)]
 pub struct NpcUIMenu {
-    #[educe(Default(expression = Vec::with_capacity(MAX_NPCS)))]
-    pub list: Vec<ListData>,
-    pub list_selected: Option<ListData>,
+    pub filters: NpcFilters,
 }
 
 impl NpcUIMenu {
-    pub fn layout(&self) -> Element<Message> {
-        row![
-            PickList::new(
-                &self.list[..],
-                self.list_selected.clone(),
-                Message::ListSelect,
-            )
-            .width(Length::Fill),
-            button("Revert").on_press(Message::RevertButtonPress),
-            button("Save").on_press(Message::SaveButtonPress),
-            button("Save All").on_press(Message::SaveAllButtonPress)
+    /// The record list along with its filters.
+    pub fn list_layout<'a>(
+        &'a self,
+        records: Element<'a, Message>,
+        behaviours: &'a [AIBehavior],
+    ) -> Element<'a, Message> {
+        column![
+            row![
+                PickList::new(
+                    FilterChoice::list(behaviours),
+                    Some(FilterChoice::of(self.filters.behaviour)),
+                    |choice| Message::FilterBehaviour(choice.value()),
+                )
+                .width(Length::Fill),
+                button("Clear").on_press(Message::ClearFilters),
+            ]
+            .spacing(5),
+            row![
+                text("Level"),
+                self.level_input(0),
+                text("to"),
+                self.level_input(1),
+            ]
+            .spacing(5)
+            .align_y(Alignment::Center),
+            records,
         ]
-        .width(Length::Fill)
-        .spacing(5)
+        .spacing(6)
+        .width(Length::Fixed(320.0))
         .into()
     }
+
+    fn level_input(&self, id: usize) -> Element<'_, Message> {
+        let value = self.filters.level[id].map(|level| level.to_string());
+
+        text_input("Any", value.as_deref().unwrap_or_default())
+            .on_input(move |input| Message::FilterLevel((id, input)))
+            .width(Length::Fixed(60.0))
+            .into()
+    }
 }
@@ -1,4 +1,5 @@
 use crate::npc::*;
+use ascending_data::*;
 use ascending_types::*;
 use ascending_ui::*;
 use iced::{
@@ -37,7 +38,7 @@ pub struct NpcUISettings {
 }
 
 impl NpcUISettings {
-    pub fn layout(&self, _npc: &NpcData) -> Element<Message> {
+    pub fn layout(&self, _npc: &NpcData) -> Element<'_, Message> {
         column![
             row![
                 Rule::horizontal(0),
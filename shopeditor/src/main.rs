@@ -4,20 +4,24 @@
 extern crate educe;
 extern crate serde_big_array;
 
-mod config;
 mod shop;
 
-use config::*;
-
+use ascending_data::*;
 use ascending_logger::*;
 use ascending_ui::*;
 use iced::{
+    keyboard, time,
     widget::{Column, Container},
-    Element, Length,
+    window, Element, Length, Subscription, Task,
 };
 use iced_aw::iced_fonts;
 use shop::*;
-use std::fs;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+const CONFIG_FILE: &str = "./shop_config.json";
 
 pub fn main() -> Result<iced::Result, String> {
     let logger = Box::new(MyLogger::new("shop_editor_log.txt"));
@@ -26,44 +30,76 @@ pub fn main() -> Result<iced::Result, String> {
     info!("starting up");
     info!("Setting Panic Hook");
 
-    std::panic::set_hook(Box::new(|panic_info| {
+    let journal = Arc::new(Mutex::new(Journal::<ShopData>::default()));
+    let panic_journal = journal.clone();
+
+    std::panic::set_hook(Box::new(move |panic_info| {
         let bt = backtrace::Backtrace::new();
 
         error!("PANIC: {}, BACKTRACE: {:?}", panic_info, bt);
+
+        // Keep whatever was unsaved so it can be restored on the next launch.
+        if let Ok(mut journal) = panic_journal.try_lock() {
+            match journal.write() {
+                Ok(()) => error!("Unsaved records were written to the recovery journal"),
+                Err(e) => error!("Could not write the recovery journal, err {}", e),
+            }
+        }
     }));
 
-    if let Err(e) = fs::create_dir_all("./data/shops/json/") {
-        return Err(format!("Err: {:?}", e));
+    let mut config = EditorConfig::load(CONFIG_FILE);
+    config.project = config.project.with_args(std::env::args().skip(1))?;
+    config.project.check_startup()?;
+    config.project.remember(&mut config.recent_projects);
+
+    if let Err(e) = config.save(CONFIG_FILE) {
+        warn!("Could not save config, err {}", e);
     }
 
+    info!("Using project {}", config.project);
+    ShopData::create_folders(&config.project.data)?;
+
     info!("Checked or Created Directorys");
-    ShopData::create_files()?;
 
-    info!("Checked or Created Files");
+    let pages = Pages::new(config, journal)
+        .inspect_err(|e| error!("Could not open the project, err {}", e))?;
 
     Ok(iced::application("Shop Editor", Pages::update, Pages::view)
+        .subscription(Pages::subscription)
+        .exit_on_close_request(false)
         .font(iced_fonts::REQUIRED_FONT_BYTES)
-        .run())
+        .run_with(move || (pages, Task::none())))
 }
 
 pub struct Pages {
     page: Box<dyn UiRenderer<Message = shop::Message>>,
 }
 
-impl Default for Pages {
-    fn default() -> Self {
-        Self {
-            page: Box::new(ShopUI::new()),
-        }
+impl Pages {
+    fn new(config: EditorConfig, journal: Arc<Mutex<Journal<ShopData>>>) -> Result<Self, String> {
+        Ok(Self {
+            page: Box::new(ShopUI::new(config, journal)?),
+        })
     }
-}
 
-impl Pages {
-    fn update(&mut self, message: Message) {
-        self.page.update(message);
+    fn update(&mut self, message: Message) -> Task<Message> {
+        self.page.update(message)
+    }
+
+    fn subscription(&self) -> Subscription<Message> {
+        Subscription::batch([
+            keyboard::on_key_press(|key, modifiers| {
+                history_shortcut(key, modifiers)
+                    .map(|action| Message::Editor(EditorMessage::History(action)))
+            }),
+            window::close_requests()
+                .map(|window| Message::Editor(EditorMessage::CloseRequested(window))),
+            time::every(Duration::from_secs(AUTOSAVE_SECS))
+                .map(|_| Message::Editor(EditorMessage::Autosave)),
+        ])
     }
 
-    fn view(&self) -> Element<Message> {
+    fn view(&self) -> Element<'_, Message> {
         let page = self.page.view();
 
         let content: Element<_> = Column::new()
@@ -2,10 +2,9 @@ use ascending_ui::*;
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Message {
-    SaveButtonPress,
-    SaveAllButtonPress,
-    RevertButtonPress,
-    ListSelect(ListData),
+    Editor(EditorMessage),
+    FilterNonEmpty(bool),
+    ItemPicker(PickerMessage),
     GenericInput((usize, NumInputMessage<u16>)),
     GenericInput64((usize, NumInputMessage<u64>)),
     NameInput(String),
@@ -2,10 +2,8 @@ mod messages;
 mod shop_ui;
 mod shop_ui_generic;
 mod shop_ui_menu;
-mod shopdata;
 
 pub use messages::*;
 pub use shop_ui::*;
 pub use shop_ui_generic::*;
 pub use shop_ui_menu::*;
-pub use shopdata::*;
@@ -1,78 +1,94 @@
-use crate::{load_config, shop::*, ConfigData};
+use crate::{shop::*, CONFIG_FILE};
+use ascending_data::*;
 use ascending_types::*;
 use ascending_ui::*;
+use std::sync::{Arc, Mutex};
 
 use iced::{
-    widget::{column, scrollable, Container},
-    Element, Length,
+    widget::{row, scrollable, Column, Container},
+    Element, Length, Task,
 };
 
-#[allow(dead_code)]
-#[derive(Educe)]
-#[educe(Default)]
 pub struct ShopUI {
-    #[educe(Default(expression = Vec::with_capacity(MAX_SHOPS)))]
-    pub data: Vec<(ShopData, bool)>,
+    core: EditorCore<ShopData>,
     menu: ShopUiMenu,
     generic: ShopUiGeneric, //Generic Shop Data.
-    currentid: usize,
     current_shopid: usize,
-    config: ConfigData,
+    item_picker: RecordPicker,
 }
 
 impl UiRenderer for ShopUI {
     type Message = Message;
-    fn update(&mut self, msg: Message) {
+
+    fn update(&mut self, msg: Message) -> Task<Message> {
+        let task = self.handle_message(msg);
+        let filters = &self.menu.filters;
+
+        self.core.refresh_records(|data| filters.passes(data));
+        self.core.track_unsaved();
+        task
+    }
+
+    fn view(&self) -> Element<'_, Message> {
+        self.layout()
+    }
+}
+
+impl ShopUI {
+    fn handle_message(&mut self, msg: Message) -> Task<Message> {
+        let id = self.core.currentid;
+        let before = self.core.data[id].0.clone();
+        let merge = matches!(msg, Message::NameInput(_));
+
         match msg {
-            Message::SaveAllButtonPress => {
-                self.save_all();
-                return;
-            }
-            Message::SaveButtonPress => {
-                if self.config.save_json {
-                    self.data[self.currentid]
-                        .0
-                        .save_file(self.currentid)
-                        .unwrap();
+            Message::Editor(msg) => {
+                let (generic, item_picker) = (&mut self.generic, &mut self.item_picker);
+                let task = self.core.update(msg, |config, project_config| {
+                    load_items(generic, item_picker, config, project_config);
+                    Ok(())
+                });
+
+                if self.core.take_refresh() {
+                    self.set_object_to_layout(self.core.currentid);
                 }
-                self.data[self.currentid]
-                    .0
-                    .save_bin_file(self.currentid)
-                    .unwrap();
-                return;
+
+                return task.map(Message::Editor);
             }
-            Message::RevertButtonPress => {
-                let shop = ShopData::load_file(self.currentid).unwrap();
-                self.data[self.currentid].0 = shop.0;
-                self.data[self.currentid].1 = false;
-                self.set_object_to_layout(self.currentid);
-                return;
+            Message::FilterNonEmpty(non_empty) => {
+                self.menu.filters.non_empty = non_empty;
+                self.core.filters_changed();
+                return Task::none();
             }
-            Message::ListSelect(data) => {
-                self.currentid = data.id;
-                self.menu.list_selected = Some(data);
-                self.set_object_to_layout(self.currentid);
-                return;
+            Message::ItemPicker(PickerMessage::List(RecordListMessage::Select(item))) => {
+                let Some(value) = self
+                    .item_picker
+                    .pick()
+                    .and_then(|_| u16::try_from(item).ok())
+                else {
+                    return Task::none();
+                };
+
+                self.generic.item_index.value = value;
+                self.core.current_mut().item[self.current_shopid].index = value;
+            }
+            Message::ItemPicker(msg) => {
+                return self.item_picker.update(msg).map(Message::ItemPicker);
             }
             Message::NameInput(value) => {
                 if value.len() < 64 {
                     self.generic.txt_value = value;
-                    self.data[self.currentid]
-                        .0
+                    self.core
+                        .current_mut()
                         .name
                         .clone_from(&self.generic.txt_value);
-                    self.menu.list[self.currentid]
-                        .name
-                        .clone_from(&self.generic.txt_value);
-                    self.menu.list_selected = Some(self.menu.list[self.currentid].clone());
                 } else {
-                    return;
+                    return Task::none();
                 }
             }
             Message::SlotSelect(slot) => {
                 self.generic.slot_selected = Some(slot);
                 self.current_shopid = slot as usize;
-                self.load_shop_slot(self.currentid, self.current_shopid);
+                self.load_shop_slot(self.core.currentid, self.current_shopid);
             }
             Message::GenericInput((id, data)) => {
                 let value = data.get_data();
@@ -80,17 +96,17 @@ impl UiRenderer for ShopUI {
                 match id {
                     1 => {
                         self.generic.max_shop_item.value = value;
-                        self.data[self.currentid].0.max_item = value;
+                        self.core.current_mut().max_item = value;
                     }
                     2 => {
                         self.generic.item_index.value = value;
-                        self.data[self.currentid].0.item[self.current_shopid].index = value;
+                        self.core.current_mut().item[self.current_shopid].index = value;
                     }
                     3 => {
                         self.generic.item_amount.value = value;
-                        self.data[self.currentid].0.item[self.current_shopid].amount = value;
+                        self.core.current_mut().item[self.current_shopid].amount = value;
                     }
-                    _ => return,
+                    _ => return Task::none(),
                 }
             }
             Message::GenericInput64((id, data)) => {
@@ -99,63 +115,53 @@ impl UiRenderer for ShopUI {
                 match id {
                     1 => {
                         self.generic.item_price.value = value;
-                        self.data[self.currentid].0.item[self.current_shopid].price = value;
+                        self.core.current_mut().item[self.current_shopid].price = value;
                     }
-                    _ => return,
+                    _ => return Task::none(),
                 }
             }
         }
 
-        self.data[self.currentid].1 = true;
-    }
+        self.core.edited(id, before, merge);
 
-    fn view(&self) -> Element<Message> {
-        self.layout()
+        Task::none()
     }
-}
-
-impl ShopUI {
-    pub fn new() -> Self {
-        let config: ConfigData = load_config();
 
+    /// Opens the project of the config, failing if its records can not be read.
+    pub fn new(
+        config: EditorConfig,
+        journal: Arc<Mutex<Journal<ShopData>>>,
+    ) -> Result<Self, String> {
+        let tools = EditorTools {
+            sprites: false,
+            referenced: false,
+        };
         let mut ui = ShopUI {
-            data: ShopData::load_files(config.save_json).unwrap(),
-            config,
-            ..Default::default()
+            core: EditorCore::new(config, CONFIG_FILE, journal, tools),
+            menu: ShopUiMenu::default(),
+            generic: ShopUiGeneric::default(),
+            current_shopid: 0,
+            item_picker: RecordPicker::default(),
         };
 
-        for (i, v) in ui.data.iter().enumerate() {
-            ui.menu.list.push(ListData::new(i, v.0.name.clone()));
-        }
-        ui.menu.list_selected = Some(ui.menu.list[0].clone());
-
         for i in 0..MAX_SHOP_ITEM {
             ui.generic.slot_list.push(i as u16);
         }
 
+        let (generic, item_picker) = (&mut ui.generic, &mut ui.item_picker);
+        ui.core.load_project(|config, project_config| {
+            load_items(generic, item_picker, config, project_config);
+            Ok(())
+        })?;
         ui.set_object_to_layout(0);
-        ui
-    }
-
-    pub fn save_all(&mut self) {
-        for (i, v) in self.data.iter().enumerate() {
-            if !v.1 {
-                continue;
-            }
 
-            if self.config.save_json {
-                if let Err(e) = v.0.save_file(i) {
-                    println!("Could not save Shop {}, err {}", i, e);
-                }
-            }
-            if let Err(e) = v.0.save_bin_file(i) {
-                println!("Could not save bin Shop {}, err {}", i, e);
-            }
-        }
+        let filters = &ui.menu.filters;
+        ui.core.refresh_records(|data| filters.passes(data));
+        Ok(ui)
     }
 
     fn set_object_to_layout(&mut self, index: usize) {
-        self.generic.max_shop_item.value = self.data[index].0.max_item;
+        self.generic.max_shop_item.value = self.core.data[index].0.max_item;
 
         self.generic.slot_selected = Some(0);
         self.current_shopid = 0;
@@ -163,23 +169,49 @@ impl ShopUI {
     }
 
     fn load_shop_slot(&mut self, index: usize, shopslot: usize) {
-        self.generic.item_index.value = self.data[index].0.item[shopslot].index;
-        self.generic.item_amount.value = self.data[index].0.item[shopslot].amount;
-        self.generic.item_price.value = self.data[index].0.item[shopslot].price;
+        self.generic.item_index.value = self.core.data[index].0.item[shopslot].index;
+        self.generic.item_amount.value = self.core.data[index].0.item[shopslot].amount;
+        self.generic.item_price.value = self.core.data[index].0.item[shopslot].price;
     }
 
-    fn layout(&self) -> Element<Message> {
+    fn layout(&self) -> Element<'_, Message> {
         Container::new(
-            column![
-                self.menu.layout(),
-                scrollable(
-                    Container::new(self.generic.layout())
-                        .padding(5)
-                        .width(Length::Fill)
-                        .center_x(Length::Fill)
+            Column::new()
+                .push_maybe(
+                    self.core
+                        .notices_layout()
+                        .map(|view| view.map(Message::Editor)),
+                )
+                .push(self.core.menu_layout().map(Message::Editor))
+                .push_maybe(
+                    self.core
+                        .panels_layout()
+                        .map(|view| view.map(Message::Editor)),
                 )
-            ]
-            .spacing(20),
+                .push_maybe(
+                    self.item_picker
+                        .view(usize::from(self.generic.item_index.value))
+                        .map(|picker| picker.map(Message::ItemPicker)),
+                )
+                .push(
+                    row![
+                        self.menu
+                            .list_layout(self.core.list_layout().map(Message::Editor)),
+                        scrollable(
+                            Container::new(
+                                self.generic.layout(
+                                    self.item_picker
+                                        .name_of(usize::from(self.generic.item_index.value)),
+                                )
+                            )
+                            .padding(5)
+                            .width(Length::Fill)
+                            .center_x(Length::Fill),
+                        ),
+                    ]
+                    .spacing(10),
+                )
+                .spacing(20),
         )
         .width(Length::Fill)
         .height(Length::Fill)
@@ -187,3 +219,22 @@ impl ShopUI {
         .into()
     }
 }
+
+/// Reads the item limit and the item names and sprites for the item picker.
+/// Items are only shown here, never saved.
+fn load_items(
+    generic: &mut ShopUiGeneric,
+    item_picker: &mut RecordPicker,
+    config: &EditorConfig,
+    project_config: &ProjectConfig,
+) {
+    let items: Vec<ItemData> = DataSet::load_all(&config.project.data, config.source);
+
+    generic.item_limit = project_config.limit::<ItemData>();
+    item_picker.set_records(
+        &config.project.resources,
+        items
+            .into_iter()
+            .map(|item| (item.name, i64::from(item.sprite))),
+    );
+}
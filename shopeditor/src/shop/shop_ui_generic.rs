@@ -3,7 +3,7 @@ use ascending_types::{MAX_ITEMS, MAX_SHOP_ITEM};
 use ascending_ui::*;
 use iced::{
     alignment::Alignment,
-    widget::{column, row, text_input, PickList, Rule},
+    widget::{button, column, row, text, text_input, PickList, Rule},
     Element, Length,
 };
 
@@ -25,10 +25,15 @@ pub struct ShopUiGeneric {
     pub item_amount: NumInput<u16, Message>,
     #[educe(Default(expression = NumInput::new(0)))]
     pub item_price: NumInput<u64, Message>,
+    /// Item limit of the open project, bounding the item index input.
+    #[educe(Default(expression = MAX_ITEMS))]
+    pub item_limit: usize,
 }
 
 impl ShopUiGeneric {
-    pub fn layout(&self) -> Element<Message> {
+    /// `item_name` is the name of the item in the selected slot, None when
+    /// it does not exist.
+    pub fn layout(&self, item_name: Option<&str>) -> Element<'_, Message> {
         let row0 = row![
             "Name:",
             text_input("Name", &self.txt_value)
@@ -46,8 +51,16 @@ impl ShopUiGeneric {
             "Slot:",
             PickList::new(&self.slot_list[..], self.slot_selected, Message::SlotSelect,),
             "Index:",
-            self.item_index
-                .view(2, 0, MAX_ITEMS as u16, 1, Message::GenericInput, None),
+            self.item_index.view(
+                2,
+                0,
+                self.item_limit.saturating_sub(1).min(u16::MAX as usize) as u16,
+                1,
+                Message::GenericInput,
+                None
+            ),
+            button("Pick").on_press(Message::ItemPicker(PickerMessage::Open(0))),
+            text(item_name.unwrap_or("Missing item").to_owned()),
             "Amount:",
             self.item_amount
                 .view(3, 0, u16::MAX, 1, Message::GenericInput, None),
@@ -55,7 +68,8 @@ impl ShopUiGeneric {
             self.item_price
                 .view(1, 0, u64::MAX, 1, Message::GenericInput64, None),
         ]
-        .spacing(5);
+        .spacing(5)
+        .align_y(Alignment::Center);
 
         column![row0, Rule::horizontal(0), row1]
             .spacing(6)
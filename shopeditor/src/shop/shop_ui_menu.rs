@@ -1,34 +1,37 @@
 use crate::shop::*;
-use ascending_types::*;
-use ascending_ui::*;
+use ascending_data::*;
 use iced::{
-    widget::{button, row, PickList},
+    widget::{checkbox, column},
     Element, Length,
 };
 
-#[derive(Educe)]
-#[educe(Default)]
+/// Narrows the record list down to shops selling something.
+#[derive(Default)]
+pub struct ShopFilters {
+    pub non_empty: bool,
+}
+
+impl ShopFilters {
+    pub fn passes(&self, data: &ShopData) -> bool {
+        !self.non_empty || data.max_item > 0
+    }
+}
+
+#[derive(Default)]
 pub struct ShopUiMenu {
-    #[educe(Default(expression = Vec::with_capacity(MAX_SHOPS)))]
-    pub list: Vec<ListData>,
-    pub list_selected: Option<ListData>,
+    pub filters: ShopFilters,
 }
 
 impl ShopUiMenu {
-    pub fn layout(&self) -> Element<Message> {
-        row![
-            PickList::new(
-                &self.list[..],
-                self.list_selected.clone(),
-                Message::ListSelect,
-            )
-            .width(Length::Fill),
-            button("Revert").on_press(Message::RevertButtonPress),
-            button("Save").on_press(Message::SaveButtonPress),
-            button("Save All").on_press(Message::SaveAllButtonPress)
+    /// The record list along with its filters.
+    pub fn list_layout<'a>(&'a self, records: Element<'a, Message>) -> Element<'a, Message> {
+        column![
+            checkbox("Only shops with items", self.filters.non_empty)
+                .on_toggle(Message::FilterNonEmpty),
+            records,
         ]
-        .width(Length::Fill)
-        .spacing(5)
+        .spacing(6)
+        .width(Length::Fixed(320.0))
         .into()
     }
 }
@@ -0,0 +1,281 @@
+use crate::ItemTypes;
+use serde::*;
+
+/// Amount of values within the raw data array of an item.
+pub const ITEM_DATA_LEN: usize = 20;
+
+/// Where a payload field is kept within the raw data array, along with how
+/// it is shown.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PayloadField {
+    pub index: usize,
+    /// Name of the struct field, such as `melee_damage`.
+    pub meaning: &'static str,
+    pub label: &'static str,
+    pub min: i16,
+    pub max: i16,
+    pub tooltip: &'static str,
+}
+
+/// Declares a payload struct along with the index, label and range of each
+/// of its fields.
+macro_rules! payload {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $($field:ident: $index:literal, $label:literal, $min:expr, $max:expr, $tooltip:literal;)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+        pub struct $name {
+            $(pub $field: i16,)*
+        }
+
+        impl $name {
+            pub const FIELDS: &'static [PayloadField] = &[$(PayloadField {
+                index: $index,
+                meaning: stringify!($field),
+                label: $label,
+                min: $min,
+                max: $max,
+                tooltip: $tooltip,
+            },)*];
+
+            pub fn from_data(data: &[i16; ITEM_DATA_LEN]) -> Self {
+                Self {
+                    $($field: data[$index],)*
+                }
+            }
+
+            pub fn write_data(&self, data: &mut [i16; ITEM_DATA_LEN]) {
+                $(data[$index] = self.$field;)*
+            }
+        }
+    };
+}
+
+payload! {
+    /// Data of weapons and tools.
+    WeaponStats {
+        melee_damage: 0, "Melee Damage", 0, i16::MAX, "";
+        magic_damage: 1, "Magic Damage", 0, i16::MAX, "";
+        durability: 2, "Durability", 0, i16::MAX, "";
+    }
+}
+
+payload! {
+    /// Data of everything worn: accessories, helmets, armor, trousers and
+    /// boots.
+    ArmorStats {
+        melee_defense: 0, "Melee Defense", 0, i16::MAX, "";
+        magic_defense: 1, "Magic Defense", 0, i16::MAX, "";
+        durability: 2, "Durability", 0, i16::MAX, "";
+    }
+}
+
+payload! {
+    /// Data of consumables.
+    ConsumeEffect {
+        melee_damage: 0, "Melee Damage", 0, i16::MAX, "";
+        magic_damage: 1, "Magic Damage", 0, i16::MAX, "";
+        durability: 2, "Durability", 0, i16::MAX, "";
+    }
+}
+
+payload! {
+    TrapStats {
+        melee_damage: 0, "Melee Damage", 0, i16::MAX, "";
+        magic_damage: 1, "Magic Damage", 0, i16::MAX, "";
+    }
+}
+
+payload! {
+    BookData {
+        book_id: 0, "Book ID", 0, i16::MAX, "";
+    }
+}
+
+payload! {
+    QuestItemData {
+        special_id: 0, "Special ID", 0, i16::MAX, "";
+        reusable: 1, "Reusable", 0, 1, "1 if the item is kept after use";
+    }
+}
+
+payload! {
+    KeyData {
+        key_id: 0, "Key ID", 0, i16::MAX, "";
+        reusable: 1, "Reusable", 0, 1, "1 if the item is kept after use";
+    }
+}
+
+/// Typed data of an item, picked by its `ItemTypes`. Types without data,
+/// such as cosmetics, are `None`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ItemPayload {
+    #[default]
+    None,
+    Weapon(WeaponStats),
+    Armor(ArmorStats),
+    Consume(ConsumeEffect),
+    Tool(WeaponStats),
+    Trap(TrapStats),
+    Book(BookData),
+    Questitem(QuestItemData),
+    Key(KeyData),
+}
+
+impl ItemPayload {
+    pub fn from_data(item_type: ItemTypes, data: &[i16; ITEM_DATA_LEN]) -> Self {
+        match item_type {
+            ItemTypes::Weapon => ItemPayload::Weapon(WeaponStats::from_data(data)),
+            ItemTypes::Accessory
+            | ItemTypes::Helmet
+            | ItemTypes::Armor
+            | ItemTypes::Trouser
+            | ItemTypes::Boots => ItemPayload::Armor(ArmorStats::from_data(data)),
+            ItemTypes::Consume => ItemPayload::Consume(ConsumeEffect::from_data(data)),
+            ItemTypes::Tool => ItemPayload::Tool(WeaponStats::from_data(data)),
+            ItemTypes::Trap => ItemPayload::Trap(TrapStats::from_data(data)),
+            ItemTypes::Book => ItemPayload::Book(BookData::from_data(data)),
+            ItemTypes::Questitem => ItemPayload::Questitem(QuestItemData::from_data(data)),
+            ItemTypes::Key => ItemPayload::Key(KeyData::from_data(data)),
+            ItemTypes::None
+            | ItemTypes::Cosmetic
+            | ItemTypes::Blueprint
+            | ItemTypes::Heavyobject
+            | ItemTypes::Count => ItemPayload::None,
+        }
+    }
+
+    /// Writes the payload fields into a data array, leaving the other
+    /// indexes as they are.
+    pub fn write_data(&self, data: &mut [i16; ITEM_DATA_LEN]) {
+        match self {
+            ItemPayload::None => {}
+            ItemPayload::Weapon(stats) | ItemPayload::Tool(stats) => stats.write_data(data),
+            ItemPayload::Armor(stats) => stats.write_data(data),
+            ItemPayload::Consume(effect) => effect.write_data(data),
+            ItemPayload::Trap(stats) => stats.write_data(data),
+            ItemPayload::Book(book) => book.write_data(data),
+            ItemPayload::Questitem(quest) => quest.write_data(data),
+            ItemPayload::Key(key) => key.write_data(data),
+        }
+    }
+
+    /// Whether this is the payload an item type has. Armor covers several
+    /// item types, so the item type can not be told from the payload.
+    pub fn fits(&self, item_type: ItemTypes) -> bool {
+        std::mem::discriminant(self)
+            == std::mem::discriminant(&Self::from_data(item_type, &[0; ITEM_DATA_LEN]))
+    }
+
+    /// Fields of the payload an item type has.
+    pub fn fields(item_type: ItemTypes) -> &'static [PayloadField] {
+        match Self::from_data(item_type, &[0; ITEM_DATA_LEN]) {
+            ItemPayload::None => &[],
+            ItemPayload::Weapon(_) | ItemPayload::Tool(_) => WeaponStats::FIELDS,
+            ItemPayload::Armor(_) => ArmorStats::FIELDS,
+            ItemPayload::Consume(_) => ConsumeEffect::FIELDS,
+            ItemPayload::Trap(_) => TrapStats::FIELDS,
+            ItemPayload::Book(_) => BookData::FIELDS,
+            ItemPayload::Questitem(_) => QuestItemData::FIELDS,
+            ItemPayload::Key(_) => KeyData::FIELDS,
+        }
+    }
+}
+
+/// Typed view of the raw data array of an item. Values at indexes the
+/// payload does not use are kept within `rest`, so converting back gives
+/// the same array.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedItemData {
+    pub payload: ItemPayload,
+    pub rest: [i16; ITEM_DATA_LEN],
+}
+
+impl TypedItemData {
+    pub fn new(item_type: ItemTypes, data: &[i16; ITEM_DATA_LEN]) -> Self {
+        let mut rest = *data;
+
+        for field in ItemPayload::fields(item_type) {
+            rest[field.index] = 0;
+        }
+
+        Self {
+            payload: ItemPayload::from_data(item_type, data),
+            rest,
+        }
+    }
+
+    pub fn to_data(&self) -> [i16; ITEM_DATA_LEN] {
+        let mut data = self.rest;
+        self.payload.write_data(&mut data);
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item_types() -> impl Iterator<Item = ItemTypes> {
+        (0..ItemTypes::Count as usize).map(ItemTypes::from_index)
+    }
+
+    fn data() -> [i16; ITEM_DATA_LEN] {
+        std::array::from_fn(|i| i as i16 + 1)
+    }
+
+    #[test]
+    fn typed_data_round_trips_every_item_type() {
+        for item_type in item_types() {
+            let typed = TypedItemData::new(item_type, &data());
+
+            assert_eq!(typed.to_data(), data(), "{:?}", item_type);
+            assert_eq!(
+                ItemPayload::from_data(item_type, &typed.to_data()),
+                typed.payload,
+                "{:?}",
+                item_type
+            );
+        }
+    }
+
+    #[test]
+    fn payload_fields_are_split_from_the_rest() {
+        for item_type in item_types() {
+            let typed = TypedItemData::new(item_type, &data());
+
+            for field in ItemPayload::fields(item_type) {
+                assert_eq!(typed.rest[field.index], 0, "{:?}", item_type);
+            }
+
+            let mut written = [0; ITEM_DATA_LEN];
+            typed.payload.write_data(&mut written);
+
+            for (index, value) in written.iter().enumerate() {
+                let used = ItemPayload::fields(item_type)
+                    .iter()
+                    .any(|field| field.index == index);
+
+                assert_eq!(*value, if used { data()[index] } else { 0 });
+            }
+        }
+    }
+
+    #[test]
+    fn payload_changes_reach_the_data() {
+        let mut typed = TypedItemData::new(ItemTypes::Weapon, &data());
+
+        if let ItemPayload::Weapon(stats) = &mut typed.payload {
+            stats.durability = 500;
+        }
+
+        let mut expected = data();
+        expected[2] = 500;
+
+        assert_eq!(typed.to_data(), expected);
+    }
+}
@@ -4,6 +4,7 @@ mod date;
 mod duration;
 mod enums;
 mod instant;
+mod item_payload;
 mod position;
 mod rgb;
 mod sharedstructs;
@@ -12,6 +13,7 @@ pub use date::MyDate;
 pub use duration::MyDuration;
 pub use enums::*;
 pub use instant::MyInstant;
+pub use item_payload::*;
 pub use position::*;
 pub use rgb::*;
 pub use sharedstructs::*;
@@ -0,0 +1,71 @@
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, container, row, scrollable, text, Column, Row, Rule},
+    Element, Length,
+};
+
+const ROW_HEIGHT: f32 = 36.0;
+const MAX_HEIGHT: f32 = 250.0;
+
+/// A line of an action list, its label followed by one button per action.
+pub struct ActionRow<Message> {
+    pub label: String,
+    pub actions: Vec<(&'static str, Message)>,
+}
+
+impl<Message> ActionRow<Message> {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self {
+            label: label.into(),
+            actions: Vec::new(),
+        }
+    }
+
+    pub fn action(mut self, name: &'static str, message: Message) -> Self {
+        self.actions.push((name, message));
+        self
+    }
+}
+
+/// Boxed and scrollable list of rows with buttons, plus a close button.
+pub fn action_list<'a, Message: Clone + 'a>(
+    title: &str,
+    rows: Vec<ActionRow<Message>>,
+    on_close: Message,
+) -> Element<'a, Message> {
+    let mut list = Column::new().spacing(4);
+    let height = (rows.len().max(1) as f32 * ROW_HEIGHT).min(MAX_HEIGHT);
+
+    if rows.is_empty() {
+        list = list.push(text("Nothing to show."));
+    }
+
+    for entry in rows {
+        let line = entry.actions.into_iter().fold(
+            Row::new()
+                .push(text(entry.label).width(Length::Fill))
+                .spacing(6)
+                .align_y(Alignment::Center),
+            |line, (name, message)| line.push(button(name).on_press(message)),
+        );
+
+        list = list.push(line);
+    }
+
+    container(
+        column![
+            row![
+                text(title.to_owned()).width(Length::Fill),
+                button("Close").on_press(on_close)
+            ]
+            .spacing(10),
+            Rule::horizontal(0),
+            scrollable(list).height(Length::Fixed(height))
+        ]
+        .spacing(6),
+    )
+    .style(container::rounded_box)
+    .padding(10)
+    .width(Length::Fill)
+    .into()
+}
@@ -0,0 +1,170 @@
+use crate::*;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, row, text, text_input, PickList},
+    Element, Length,
+};
+
+/// How the input of a bulk edit is used.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum BulkMode {
+    /// Sets the field to the input, a number or `true` or `false`.
+    #[default]
+    Set,
+    /// Works the new value out from the input, an expression of the current
+    /// value `x`.
+    Expression,
+}
+
+impl BulkMode {
+    pub const ALL: [BulkMode; 2] = [BulkMode::Set, BulkMode::Expression];
+}
+
+impl std::fmt::Display for BulkMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BulkMode::Set => f.write_str("Set to"),
+            BulkMode::Expression => f.write_str("Expression of x"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkEditMessage {
+    Show,
+    Close,
+    Field(String),
+    Mode(BulkMode),
+    Input(String),
+    Preview,
+    Apply,
+    Cancel,
+}
+
+/// Bulk edit the editor was asked to work out or apply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BulkEditRequest {
+    /// Works out the edit for the marked records to preview it.
+    Preview {
+        field: String,
+        mode: BulkMode,
+        input: String,
+    },
+    /// Makes the changes shown within the preview.
+    Apply,
+}
+
+/// Changes one numeric or boolean field of every marked record, shown as a
+/// preview before anything is changed.
+#[derive(Debug, Default)]
+pub struct BulkEditor {
+    pub show: bool,
+    /// Fields that can be changed, set by the editor.
+    pub fields: Vec<String>,
+    pub field: Option<String>,
+    pub mode: BulkMode,
+    pub input: String,
+    /// Changes of the worked out edit, set by the editor.
+    pub preview: Option<Vec<String>>,
+    /// Outcome of the latest preview or apply, set by the editor.
+    pub notice: Option<String>,
+}
+
+impl BulkEditor {
+    pub fn update(&mut self, msg: BulkEditMessage) -> Option<BulkEditRequest> {
+        match msg {
+            BulkEditMessage::Show => {
+                self.show = true;
+                self.notice = None;
+            }
+            BulkEditMessage::Close => {
+                self.show = false;
+                self.preview = None;
+            }
+            BulkEditMessage::Field(field) => {
+                self.field = Some(field);
+                self.preview = None;
+            }
+            BulkEditMessage::Mode(mode) => {
+                self.mode = mode;
+                self.preview = None;
+            }
+            BulkEditMessage::Input(input) => {
+                self.input = input;
+                self.preview = None;
+            }
+            BulkEditMessage::Preview => {
+                let Some(field) = self.field.clone() else {
+                    self.notice = Some("Pick a field to change first.".into());
+                    return None;
+                };
+
+                return Some(BulkEditRequest::Preview {
+                    field,
+                    mode: self.mode,
+                    input: self.input.clone(),
+                });
+            }
+            BulkEditMessage::Apply => return Some(BulkEditRequest::Apply),
+            BulkEditMessage::Cancel => self.preview = None,
+        }
+
+        None
+    }
+
+    /// Shows the bulk edit of `marked` records.
+    pub fn view(&self, marked: usize) -> Option<Element<'_, BulkEditMessage>> {
+        if !self.show {
+            return None;
+        }
+
+        let placeholder = match self.mode {
+            BulkMode::Set => "99 or true",
+            BulkMode::Expression => "x * 1.1",
+        };
+
+        let inputs = row![
+            text(format!("{} marked records", marked)),
+            PickList::new(&self.fields[..], self.field.clone(), BulkEditMessage::Field)
+                .placeholder("Field")
+                .width(Length::Fixed(220.0)),
+            PickList::new(&BulkMode::ALL[..], Some(self.mode), BulkEditMessage::Mode),
+            text_input(placeholder, &self.input)
+                .on_input(BulkEditMessage::Input)
+                .width(Length::Fixed(160.0))
+                .padding(3),
+            button("Preview").on_press_maybe((marked > 0).then_some(BulkEditMessage::Preview)),
+            button("Close").on_press(BulkEditMessage::Close),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center);
+
+        let mut col = column![inputs].spacing(6);
+
+        if let Some(preview) = &self.preview {
+            let rows = preview.iter().map(ActionRow::new).collect();
+
+            col = col
+                .push(action_list(
+                    "Nothing is changed until applied",
+                    rows,
+                    BulkEditMessage::Cancel,
+                ))
+                .push(
+                    row![
+                        button("Apply").on_press_maybe(
+                            (!preview.is_empty()).then_some(BulkEditMessage::Apply)
+                        ),
+                        button("Cancel").on_press(BulkEditMessage::Cancel),
+                    ]
+                    .spacing(6),
+                );
+        }
+
+        if let Some(notice) = &self.notice {
+            col = col.push(text(notice.clone()));
+        }
+
+        Some(col.into())
+    }
+}
@@ -0,0 +1,56 @@
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, container, row, scrollable, text, Column, Row, Rule},
+    Element, Length,
+};
+
+const ROW_HEIGHT: f32 = 24.0;
+const MAX_HEIGHT: f32 = 200.0;
+
+/// Boxed side by side comparison of two versions of something. Each row is a
+/// field name followed by its value within either version, the buttons of
+/// `actions` choose which version to keep.
+pub fn compare_view<'a, Message: Clone + 'a>(
+    title: &str,
+    headers: (&str, &str),
+    rows: Vec<(String, String, String)>,
+    actions: Vec<(&'static str, Message)>,
+) -> Element<'a, Message> {
+    let height = (rows.len().max(1) as f32 * ROW_HEIGHT).min(MAX_HEIGHT);
+    let line = |field: String, left: String, right: String| {
+        row![
+            text(field).width(Length::FillPortion(1)),
+            text(left).width(Length::FillPortion(2)),
+            text(right).width(Length::FillPortion(2)),
+        ]
+        .spacing(6)
+    };
+
+    let list = rows
+        .into_iter()
+        .fold(Column::new().spacing(2), |list, (field, left, right)| {
+            list.push(line(field, left, right))
+        });
+
+    let buttons = actions.into_iter().fold(
+        Row::new()
+            .push(text(title.to_owned()).width(Length::Fill))
+            .spacing(6)
+            .align_y(Alignment::Center),
+        |buttons, (name, message)| buttons.push(button(name).on_press(message)),
+    );
+
+    container(
+        column![
+            buttons,
+            Rule::horizontal(0),
+            line("Field".into(), headers.0.to_owned(), headers.1.to_owned()),
+            scrollable(list).height(Length::Fixed(height))
+        ]
+        .spacing(6),
+    )
+    .style(container::rounded_box)
+    .padding(10)
+    .width(Length::Fill)
+    .into()
+}
@@ -0,0 +1,31 @@
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, container, text, Row, Rule},
+    Element, Length,
+};
+
+/// Boxed question with one button per possible answer.
+pub fn confirm<'a, Message: Clone + 'a>(
+    title: &str,
+    question: String,
+    answers: Vec<(&'static str, Message)>,
+) -> Element<'a, Message> {
+    let buttons = answers.into_iter().fold(
+        Row::new().spacing(6).align_y(Alignment::Center),
+        |buttons, (name, message)| buttons.push(button(name).on_press(message)),
+    );
+
+    container(
+        column![
+            text(title.to_owned()),
+            Rule::horizontal(0),
+            text(question),
+            buttons
+        ]
+        .spacing(6),
+    )
+    .style(container::rounded_box)
+    .padding(10)
+    .width(Length::Fill)
+    .into()
+}
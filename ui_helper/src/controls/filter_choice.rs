@@ -0,0 +1,36 @@
+/// Entry of a filter pick list, so the filter can be set back to any value
+/// from the list itself.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum FilterChoice<T> {
+    Any,
+    Only(T),
+}
+
+impl<T: Clone> FilterChoice<T> {
+    /// `Any` followed by every value.
+    pub fn list(values: &[T]) -> Vec<Self> {
+        std::iter::once(FilterChoice::Any)
+            .chain(values.iter().cloned().map(FilterChoice::Only))
+            .collect()
+    }
+
+    pub fn of(value: Option<T>) -> Self {
+        value.map_or(FilterChoice::Any, FilterChoice::Only)
+    }
+
+    pub fn value(self) -> Option<T> {
+        match self {
+            FilterChoice::Any => None,
+            FilterChoice::Only(value) => Some(value),
+        }
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for FilterChoice<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterChoice::Any => f.write_str("Any"),
+            FilterChoice::Only(value) => value.fmt(f),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use iced::keyboard::{key::Named, Key, Modifiers};
+use std::collections::HashMap;
+
+/// Amount of changes kept on the undo stack.
+pub const MAX_HISTORY: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryMessage {
+    Undo,
+    Redo,
+}
+
+/// Maps Ctrl+Z to undo, and Ctrl+Y or Ctrl+Shift+Z to redo.
+pub fn history_shortcut(key: Key, modifiers: Modifiers) -> Option<HistoryMessage> {
+    if !modifiers.command() {
+        return None;
+    }
+
+    match key.as_ref() {
+        Key::Character("z") if modifiers.shift() => Some(HistoryMessage::Redo),
+        Key::Character("z") => Some(HistoryMessage::Undo),
+        Key::Character("y") => Some(HistoryMessage::Redo),
+        Key::Named(Named::Undo) => Some(HistoryMessage::Undo),
+        Key::Named(Named::Redo) => Some(HistoryMessage::Redo),
+        _ => None,
+    }
+}
+
+/// One step of the history, the snapshots of every record a change touched.
+#[derive(Debug)]
+struct Entry<T> {
+    records: Vec<(usize, T)>,
+    merge: bool,
+}
+
+impl<T> Entry<T> {
+    fn is_merged_into(&self, id: usize) -> bool {
+        self.merge && matches!(self.records[..], [(last, _)] if last == id)
+    }
+}
+
+/// Undo and redo stacks of whole record snapshots, tagged with the id of the
+/// record they were taken from. Works on the `(record, dirty)` lists the
+/// editors keep their records in.
+#[derive(Debug)]
+pub struct History<T> {
+    undo: Vec<Entry<T>>,
+    redo: Vec<Entry<T>>,
+    /// Saved state of the records, so undoing back to it leaves them clean.
+    saved: HashMap<usize, T>,
+}
+
+impl<T> Default for History<T> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            saved: HashMap::new(),
+        }
+    }
+}
+
+impl<T: PartialEq> History<T> {
+    /// Records the state a record had before a change. Changes flagged with
+    /// `merge`, such as typing into a text field, are folded into the entry
+    /// before them if it belongs to the same record and was flagged too.
+    pub fn push(&mut self, id: usize, before: T, merge: bool) {
+        if merge && self.undo.last().is_some_and(|last| last.is_merged_into(id)) {
+            self.redo.clear();
+            return;
+        }
+
+        self.push_entry(vec![(id, before)], merge);
+    }
+
+    /// Records the states several records had before a change made to all of
+    /// them at once, such as swapping two records, so it is undone as one.
+    pub fn push_group(&mut self, records: Vec<(usize, T)>) {
+        if !records.is_empty() {
+            self.push_entry(records, false);
+        }
+    }
+
+    fn push_entry(&mut self, records: Vec<(usize, T)>, merge: bool) {
+        self.redo.clear();
+        self.undo.push(Entry { records, merge });
+
+        if self.undo.len() > MAX_HISTORY {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Restores the latest change, returning the id of the first record it
+    /// touched.
+    pub fn undo(&mut self, data: &mut [(T, bool)]) -> Option<usize> {
+        Self::swap(&mut self.undo, &mut self.redo, &self.saved, data)
+    }
+
+    /// Applies the latest undone change again.
+    pub fn redo(&mut self, data: &mut [(T, bool)]) -> Option<usize> {
+        Self::swap(&mut self.redo, &mut self.undo, &self.saved, data)
+    }
+
+    fn swap(
+        from: &mut Vec<Entry<T>>,
+        to: &mut Vec<Entry<T>>,
+        saved: &HashMap<usize, T>,
+        data: &mut [(T, bool)],
+    ) -> Option<usize> {
+        let mut entry = from.pop()?;
+
+        for (id, snapshot) in &mut entry.records {
+            if let Some(record) = data.get_mut(*id) {
+                std::mem::swap(&mut record.0, snapshot);
+                record.1 = saved.get(id).is_none_or(|saved| *saved != record.0);
+            }
+        }
+
+        entry.merge = false;
+
+        let id = entry.records[0].0;
+        to.push(entry);
+        Some(id)
+    }
+
+    /// Remembers the state a record has on disk.
+    pub fn mark_saved(&mut self, id: usize, data: T) {
+        self.saved.insert(id, data);
+    }
+
+    /// Stops the next change from being merged into the latest entry.
+    pub fn seal(&mut self) {
+        if let Some(last) = self.undo.last_mut() {
+            last.merge = false;
+        }
+    }
+
+    /// Drops every entry of a record, such as one that was removed.
+    pub fn forget(&mut self, id: usize) {
+        for stack in [&mut self.undo, &mut self.redo] {
+            for entry in stack.iter_mut() {
+                entry.records.retain(|(record, _)| *record != id);
+            }
+
+            stack.retain(|entry| !entry.records.is_empty());
+        }
+
+        self.saved.remove(&id);
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.saved.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_is_undone_and_redone_as_one_step() {
+        let mut data = vec![(1, false), (2, false)];
+        let mut history = History::default();
+
+        history.push_group(vec![(0, data[0].0), (1, data[1].0)]);
+        data.swap(0, 1);
+
+        assert_eq!(history.undo(&mut data), Some(0));
+        assert_eq!(data, vec![(1, true), (2, true)]);
+        assert!(!history.can_undo());
+
+        assert_eq!(history.redo(&mut data), Some(0));
+        assert_eq!(data, vec![(2, true), (1, true)]);
+        assert!(!history.can_redo());
+    }
+
+    #[test]
+    fn merged_changes_fold_into_one_step() {
+        let mut data = vec![(3, false)];
+        let mut history = History::default();
+
+        history.push(0, 1, true);
+        history.push(0, 2, true);
+        history.seal();
+        history.push(0, 2, true);
+
+        assert_eq!(history.undo(&mut data), Some(0));
+        assert_eq!(data[0].0, 2);
+        assert_eq!(history.undo(&mut data), Some(0));
+        assert_eq!(data[0].0, 1);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn forget_drops_a_record_from_groups() {
+        let mut data = vec![(0, false), (0, false)];
+        let mut history = History::default();
+
+        history.push(1, 5, false);
+        history.push_group(vec![(0, 7), (1, 8)]);
+        history.forget(1);
+
+        assert_eq!(history.undo(&mut data), Some(0));
+        assert_eq!(data, vec![(7, true), (0, false)]);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn undo_back_to_the_saved_state_is_clean() {
+        let mut data = vec![(2, true)];
+        let mut history = History::default();
+
+        history.mark_saved(0, 1);
+        history.push(0, 1, false);
+
+        assert_eq!(history.undo(&mut data), Some(0));
+        assert_eq!(data, vec![(1, false)]);
+        assert_eq!(history.redo(&mut data), Some(0));
+        assert_eq!(data, vec![(2, true)]);
+
+        data[0].1 = false;
+        history.mark_saved(0, 2);
+
+        assert_eq!(history.undo(&mut data), Some(0));
+        assert_eq!(data, vec![(1, true)]);
+    }
+}
@@ -2,16 +2,22 @@
 pub struct ListData {
     pub id: usize,
     pub name: String,
+    /// Marks entries with unsaved changes.
+    pub dirty: bool,
 }
 
 impl ListData {
     pub fn new(id: usize, name: String) -> Self {
-        Self { id, name }
+        Self {
+            id,
+            name,
+            dirty: false,
+        }
     }
 }
 impl From<ListData> for String {
     fn from(list_data: ListData) -> String {
-        format!("{}: {}", list_data.id, &list_data.name)
+        format!("{}: {}", list_data.id, list_data.name)
     }
 }
 
@@ -23,6 +29,12 @@ impl std::fmt::Debug for ListData {
 
 impl std::fmt::Display for ListData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(&format!("{}: {}", self.id, self.name)[..])
+        f.write_str(&format!("{}: {}", self.id, self.name)[..])?;
+
+        if self.dirty {
+            f.write_str(" *")?;
+        }
+
+        Ok(())
     }
 }
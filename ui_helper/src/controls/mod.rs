@@ -1,7 +1,33 @@
+mod action_list;
+mod bulk_editor;
 mod checkbox;
+mod compare;
+mod confirm;
+mod filter_choice;
+mod history;
 mod list_data;
+mod notice;
 mod number_selection;
+mod project_picker;
+mod record_list;
+mod record_picker;
+mod record_tools;
+mod renumber_picker;
+mod sprite_gallery;
 
+pub use action_list::*;
+pub use bulk_editor::*;
 pub use checkbox::*;
+pub use compare::*;
+pub use confirm::*;
+pub use filter_choice::*;
+pub use history::*;
 pub use list_data::*;
+pub use notice::*;
 pub use number_selection::*;
+pub use project_picker::*;
+pub use record_list::*;
+pub use record_picker::*;
+pub use record_tools::*;
+pub use renumber_picker::*;
+pub use sprite_gallery::*;
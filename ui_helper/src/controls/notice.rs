@@ -0,0 +1,32 @@
+use iced::{
+    widget::{button, column, container, row, text, Column, Rule},
+    Element, Length,
+};
+
+/// Boxed list of messages with a dismiss button, shown above an editor.
+pub fn notice<'a, Message: Clone + 'a>(
+    title: &str,
+    lines: Vec<String>,
+    on_dismiss: Message,
+) -> Element<'a, Message> {
+    let list = lines
+        .into_iter()
+        .fold(Column::new().spacing(2), |col, line| col.push(text(line)));
+
+    container(
+        column![
+            row![
+                text(title.to_owned()).width(Length::Fill),
+                button("Dismiss").on_press(on_dismiss)
+            ]
+            .spacing(10),
+            Rule::horizontal(0),
+            list
+        ]
+        .spacing(6),
+    )
+    .style(container::rounded_box)
+    .padding(10)
+    .width(Length::Fill)
+    .into()
+}
@@ -60,7 +60,7 @@ where
         step: V,
         on_change: F,
         style: Option<Style>,
-    ) -> Element<M>
+    ) -> Element<'_, M>
     where
         F: 'static + Fn((usize, NumInputMessage<V>)) -> M + Copy,
         V: 'static,
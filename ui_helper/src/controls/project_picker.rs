@@ -0,0 +1,105 @@
+use crate::*;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, row, text, text_input},
+    Element, Length,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectMessage {
+    Show,
+    Close,
+    OpenRecent(usize),
+    DataInput(String),
+    ResourcesInput(String),
+    Open,
+}
+
+/// Project the editor was asked to switch to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProjectRequest {
+    Recent(usize),
+    Folders { data: String, resources: String },
+}
+
+/// Recent project switcher with inputs to open a new data and resource root.
+#[derive(Debug, Default)]
+pub struct ProjectPicker {
+    pub show: bool,
+    pub data_input: String,
+    pub resources_input: String,
+    pub error: Option<String>,
+}
+
+impl ProjectPicker {
+    pub fn update(&mut self, msg: ProjectMessage) -> Option<ProjectRequest> {
+        match msg {
+            ProjectMessage::Show => {
+                self.show = true;
+                self.error = None;
+            }
+            ProjectMessage::Close => self.show = false,
+            ProjectMessage::OpenRecent(index) => return Some(ProjectRequest::Recent(index)),
+            ProjectMessage::DataInput(value) => self.data_input = value,
+            ProjectMessage::ResourcesInput(value) => self.resources_input = value,
+            ProjectMessage::Open => {
+                if self.data_input.is_empty() || self.resources_input.is_empty() {
+                    self.error = Some("Both a data and a resources folder are needed.".into());
+                } else {
+                    return Some(ProjectRequest::Folders {
+                        data: self.data_input.clone(),
+                        resources: self.resources_input.clone(),
+                    });
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn view(
+        &self,
+        current: String,
+        recent: Vec<String>,
+    ) -> Option<Element<'_, ProjectMessage>> {
+        if !self.show {
+            return None;
+        }
+
+        let rows = recent
+            .into_iter()
+            .enumerate()
+            .map(|(i, label)| ActionRow::new(label).action("Open", ProjectMessage::OpenRecent(i)))
+            .collect();
+
+        let mut col = column![
+            action_list(
+                &format!("Recent Projects, current {}", current),
+                rows,
+                ProjectMessage::Close,
+            ),
+            row![
+                text("Data:"),
+                text_input("./data", &self.data_input)
+                    .on_input(ProjectMessage::DataInput)
+                    .width(Length::Fill)
+                    .padding(3),
+                text("Resources:"),
+                text_input("./resources", &self.resources_input)
+                    .on_input(ProjectMessage::ResourcesInput)
+                    .width(Length::Fill)
+                    .padding(3),
+                button("Open").on_press(ProjectMessage::Open),
+            ]
+            .spacing(6)
+            .align_y(Alignment::Center),
+        ]
+        .spacing(6);
+
+        if let Some(error) = &self.error {
+            col = col.push(text(error.clone()));
+        }
+
+        Some(col.into())
+    }
+}
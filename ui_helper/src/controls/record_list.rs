@@ -0,0 +1,268 @@
+use crate::*;
+use iced::{
+    alignment::Alignment,
+    widget::{
+        button, checkbox, column, container, row, scrollable, scrollable::RelativeOffset, text,
+        text_input, Column, Image, Space,
+    },
+    Element, Length, Task,
+};
+use std::{collections::BTreeSet, path::PathBuf};
+
+const ROW_HEIGHT: f32 = 30.0;
+const ICON_SIZE: f32 = 20.0;
+/// Rows built past either edge of the visible part of the list.
+const OVERSCAN: usize = 10;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordListMessage {
+    Search(String),
+    Scrolled {
+        offset: f32,
+        height: f32,
+    },
+    Select(usize),
+    Mark(usize, bool),
+    /// Marks every record the search and filters list.
+    MarkListed,
+    ClearMarks,
+}
+
+/// Searchable list of records. Only the rows within view are built, so it
+/// stays quick with thousands of records. The search matches names fuzzily,
+/// a number or `#number` jumps to the record with that id. A markable list
+/// has a checkbox in front of every record to work on many records at once.
+#[derive(Debug)]
+pub struct RecordList {
+    pub query: String,
+    pub markable: bool,
+    /// Ids of the marked records, kept while they are searched or filtered
+    /// out of the list.
+    pub marked: BTreeSet<usize>,
+    id: scrollable::Id,
+    offset: f32,
+    height: f32,
+    /// Ids of the listed records, best match first.
+    matches: Vec<usize>,
+}
+
+impl Default for RecordList {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            markable: false,
+            marked: BTreeSet::new(),
+            id: scrollable::Id::unique(),
+            offset: 0.0,
+            height: 800.0,
+            matches: Vec::new(),
+        }
+    }
+}
+
+impl RecordList {
+    pub fn markable() -> Self {
+        Self {
+            markable: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn update(&mut self, msg: RecordListMessage) -> Task<RecordListMessage> {
+        match msg {
+            RecordListMessage::Search(query) => {
+                self.query = query;
+                self.offset = 0.0;
+                return scrollable::snap_to(self.id.clone(), RelativeOffset::START);
+            }
+            RecordListMessage::Scrolled { offset, height } => {
+                self.offset = offset;
+                self.height = height;
+            }
+            RecordListMessage::Mark(id, marked) => {
+                if marked {
+                    self.marked.insert(id);
+                } else {
+                    self.marked.remove(&id);
+                }
+            }
+            RecordListMessage::MarkListed => self.marked.extend(self.matches.iter().copied()),
+            RecordListMessage::ClearMarks => self.marked.clear(),
+            RecordListMessage::Select(_) => {}
+        }
+
+        Task::none()
+    }
+
+    /// Works out the listed records again. `filter` tells by id whether a
+    /// record passes the filters of the editor, a jump by id ignores them.
+    pub fn refresh(&mut self, entries: &[ListData], filter: impl Fn(usize) -> bool) {
+        let query = self.query.trim();
+        let jump = query
+            .strip_prefix('#')
+            .unwrap_or(query)
+            .parse::<usize>()
+            .ok();
+
+        let mut scored: Vec<(i64, usize)> = entries
+            .iter()
+            .filter_map(|entry| {
+                if jump == Some(entry.id) {
+                    return Some((i64::MAX, entry.id));
+                }
+
+                if !filter(entry.id) {
+                    return None;
+                }
+
+                fuzzy_score(query, &entry.name).map(|score| (score, entry.id))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.matches = scored.into_iter().map(|(_, id)| id).collect();
+    }
+
+    pub fn view<'a>(
+        &'a self,
+        entries: &'a [ListData],
+        selected: usize,
+    ) -> Element<'a, RecordListMessage> {
+        self.view_with_icons(entries, selected, |_| None)
+    }
+
+    /// Same as `view` with a thumbnail in front of every record that has an
+    /// image at the path given by `icon`.
+    pub fn view_with_icons<'a>(
+        &'a self,
+        entries: &'a [ListData],
+        selected: usize,
+        icon: impl Fn(usize) -> Option<PathBuf>,
+    ) -> Element<'a, RecordListMessage> {
+        let len = self.matches.len();
+        let first = ((self.offset / ROW_HEIGHT) as usize)
+            .saturating_sub(OVERSCAN)
+            .min(len);
+        let last = (first + (self.height / ROW_HEIGHT).ceil() as usize + OVERSCAN * 2).min(len);
+
+        let rows = self.matches[first..last]
+            .iter()
+            .filter_map(|id| entries.get(*id))
+            .fold(Column::new(), |rows, entry| {
+                let style = if entry.id == selected {
+                    button::primary
+                } else {
+                    button::text
+                };
+
+                let label = match icon(entry.id).filter(|path| path.exists()) {
+                    Some(path) => row![
+                        Image::new(path)
+                            .width(Length::Fixed(ICON_SIZE))
+                            .height(Length::Fixed(ICON_SIZE)),
+                        text(entry.to_string()),
+                    ]
+                    .spacing(6)
+                    .align_y(Alignment::Center),
+                    None => row![text(entry.to_string())],
+                };
+
+                let line = button(label)
+                    .on_press(RecordListMessage::Select(entry.id))
+                    .style(style)
+                    .width(Length::Fill)
+                    .height(Length::Fixed(ROW_HEIGHT));
+
+                if !self.markable {
+                    return rows.push(line);
+                }
+
+                let id = entry.id;
+
+                rows.push(
+                    row![
+                        checkbox("", self.marked.contains(&id))
+                            .on_toggle(move |marked| RecordListMessage::Mark(id, marked)),
+                        line,
+                    ]
+                    .align_y(Alignment::Center),
+                )
+            });
+
+        let body = column![
+            Space::with_height(Length::Fixed(first as f32 * ROW_HEIGHT)),
+            rows,
+            Space::with_height(Length::Fixed((len - last) as f32 * ROW_HEIGHT)),
+        ];
+
+        container(
+            column![
+                text_input("Search by name or #id", &self.query)
+                    .on_input(RecordListMessage::Search)
+                    .padding(3),
+                text(format!("{} of {} records", len, entries.len())),
+            ]
+            .push_maybe(self.markable.then(|| {
+                row![
+                    text(format!("{} marked", self.marked.len())).width(Length::Fill),
+                    button("Mark Listed").on_press(RecordListMessage::MarkListed),
+                    button("Clear").on_press_maybe(
+                        (!self.marked.is_empty()).then_some(RecordListMessage::ClearMarks),
+                    ),
+                ]
+                .spacing(5)
+                .align_y(Alignment::Center)
+            }))
+            .push(
+                scrollable(body)
+                    .id(self.id.clone())
+                    .on_scroll(|viewport| RecordListMessage::Scrolled {
+                        offset: viewport.absolute_offset().y,
+                        height: viewport.bounds().height,
+                    })
+                    .height(Length::Fill),
+            )
+            .spacing(6),
+        )
+        .style(container::rounded_box)
+        .padding(10)
+        .height(Length::Fill)
+        .into()
+    }
+}
+
+/// Scores how well a name matches a search, None if it does not contain
+/// every character of the search in order. Matches in a row and at the start
+/// of words score higher, skipped characters lower. Case is ignored.
+pub fn fuzzy_score(query: &str, name: &str) -> Option<i64> {
+    let query: Vec<char> = query.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+
+    let mut score = 0;
+    let mut next = 0;
+    let mut previous: Option<usize> = None;
+
+    for c in query {
+        if c.is_whitespace() {
+            continue;
+        }
+
+        let found = next + name[next..].iter().position(|n| *n == c)?;
+
+        score += 1;
+
+        if previous.is_some_and(|previous| previous + 1 == found) {
+            score += 5;
+        }
+
+        if found == 0 || !name[found - 1].is_alphanumeric() {
+            score += 8;
+        }
+
+        score -= (found - next) as i64;
+        previous = Some(found);
+        next = found + 1;
+    }
+
+    Some(score)
+}
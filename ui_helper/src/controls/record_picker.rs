@@ -0,0 +1,150 @@
+use crate::*;
+use ascending_data::SpriteFolder;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, container, row, text},
+    Element, Length, Task,
+};
+use std::path::{Path, PathBuf};
+
+/// Kind of record a picker lists, telling where its sprites are kept.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+pub enum PickerKind {
+    #[default]
+    Item,
+    Npc,
+}
+
+impl PickerKind {
+    fn sprite_folder(self) -> SpriteFolder {
+        match self {
+            PickerKind::Item => SpriteFolder::ITEMS,
+            PickerKind::Npc => SpriteFolder::NPCS,
+        }
+    }
+
+    /// Path of a sprite within the resources folder of a project.
+    pub fn sprite_path(self, resources: &Path, sprite: i64) -> PathBuf {
+        self.sprite_folder().path(resources, sprite)
+    }
+
+    /// Ids of every sprite within the resources folder of a project, in
+    /// order.
+    pub fn sprite_ids(self, resources: &Path) -> Vec<i64> {
+        self.sprite_folder().sprite_ids(resources)
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            PickerKind::Item => "Pick an Item",
+            PickerKind::Npc => "Pick an Npc",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PickerMessage {
+    /// Opens the picker for a field of the editor, such as a drop or slot.
+    Open(usize),
+    Close,
+    List(RecordListMessage),
+}
+
+/// Searchable list of items or npcs with their sprites, to pick one for a
+/// field instead of typing its id. The editor handles
+/// `PickerMessage::List(RecordListMessage::Select(id))` by calling `pick`
+/// and passes everything else on to `update`.
+#[derive(Debug, Default)]
+pub struct RecordPicker {
+    pub kind: PickerKind,
+    /// Field the picker was opened for, None while closed.
+    target: Option<usize>,
+    entries: Vec<ListData>,
+    sprites: Vec<PathBuf>,
+    list: RecordList,
+}
+
+impl RecordPicker {
+    pub fn new(kind: PickerKind) -> Self {
+        Self {
+            kind,
+            ..Default::default()
+        }
+    }
+
+    /// Replaces the listed records with `(name, sprite)` pairs in id order.
+    pub fn set_records(
+        &mut self,
+        resources: &Path,
+        records: impl IntoIterator<Item = (String, i64)>,
+    ) {
+        self.entries.clear();
+        self.sprites.clear();
+
+        for (id, (name, sprite)) in records.into_iter().enumerate() {
+            self.entries.push(ListData::new(id, name));
+            self.sprites.push(self.kind.sprite_path(resources, sprite));
+        }
+
+        self.list.refresh(&self.entries, |_| true);
+    }
+
+    /// Name of a listed record.
+    pub fn name_of(&self, id: usize) -> Option<&str> {
+        self.entries.get(id).map(|entry| entry.name.as_str())
+    }
+
+    /// Field the picker is open for.
+    pub fn target(&self) -> Option<usize> {
+        self.target
+    }
+
+    /// Closes the picker, returning the field it was opened for.
+    pub fn pick(&mut self) -> Option<usize> {
+        self.target.take()
+    }
+
+    pub fn update(&mut self, msg: PickerMessage) -> Task<PickerMessage> {
+        let task = match msg {
+            PickerMessage::Open(target) => {
+                self.target = Some(target);
+                self.list.update(RecordListMessage::Search(String::new()))
+            }
+            PickerMessage::Close => {
+                self.target = None;
+                Task::none()
+            }
+            PickerMessage::List(msg) => self.list.update(msg),
+        };
+
+        self.list.refresh(&self.entries, |_| true);
+        task.map(PickerMessage::List)
+    }
+
+    /// The picker while open. `selected` is the record the field holds now.
+    pub fn view(&self, selected: usize) -> Option<Element<'_, PickerMessage>> {
+        self.target?;
+
+        Some(
+            container(
+                column![
+                    row![
+                        text(self.kind.title()).width(Length::Fill),
+                        button("Close").on_press(PickerMessage::Close),
+                    ]
+                    .align_y(Alignment::Center),
+                    self.list
+                        .view_with_icons(&self.entries, selected, |id| {
+                            self.sprites.get(id).cloned()
+                        })
+                        .map(PickerMessage::List),
+                ]
+                .spacing(6),
+            )
+            .style(container::rounded_box)
+            .padding(10)
+            .height(Length::Fixed(420.0))
+            .into(),
+        )
+    }
+}
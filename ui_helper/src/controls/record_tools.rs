@@ -0,0 +1,123 @@
+use crate::*;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, container, row, text},
+    Element,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordToolsMessage {
+    Show,
+    Close,
+    Target((usize, NumInputMessage<usize>)),
+    Copy,
+    Paste,
+    Duplicate,
+    Swap,
+    Clear,
+}
+
+/// Change to the selected record the editor was asked to make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordToolsRequest {
+    /// Puts the selected record on the clipboard as json.
+    Copy,
+    /// Replaces the selected record with the one on the clipboard.
+    Paste,
+    /// Overwrites the given record with a copy of the selected one.
+    Duplicate(usize),
+    /// Exchanges the contents of the selected and the given record.
+    Swap(usize),
+    /// Resets the selected record to its defaults.
+    Clear,
+}
+
+/// Copy, paste, duplicate, swap and clear for the selected record. Every
+/// change is an unsaved edit that can be undone, and unlike a renumber no
+/// references are rewritten.
+#[derive(Debug)]
+pub struct RecordTools {
+    pub show: bool,
+    pub target: NumInput<usize, RecordToolsMessage>,
+    /// Outcome of the latest action, set by the editor.
+    pub notice: Option<String>,
+}
+
+impl Default for RecordTools {
+    fn default() -> Self {
+        Self {
+            show: false,
+            target: NumInput::new(0),
+            notice: None,
+        }
+    }
+}
+
+impl RecordTools {
+    pub fn update(&mut self, msg: RecordToolsMessage) -> Option<RecordToolsRequest> {
+        let request = match msg {
+            RecordToolsMessage::Show => {
+                self.show = true;
+                self.notice = None;
+                return None;
+            }
+            RecordToolsMessage::Close => {
+                self.show = false;
+                return None;
+            }
+            RecordToolsMessage::Target((_, value)) => {
+                self.target.value = value.get_data();
+                return None;
+            }
+            RecordToolsMessage::Copy => RecordToolsRequest::Copy,
+            RecordToolsMessage::Paste => RecordToolsRequest::Paste,
+            RecordToolsMessage::Duplicate => RecordToolsRequest::Duplicate(self.target.value),
+            RecordToolsMessage::Swap => RecordToolsRequest::Swap(self.target.value),
+            RecordToolsMessage::Clear => RecordToolsRequest::Clear,
+        };
+
+        self.notice = None;
+        Some(request)
+    }
+
+    /// The tools for the selected record out of `count` records.
+    pub fn view(&self, selected: usize, count: usize) -> Option<Element<'_, RecordToolsMessage>> {
+        if !self.show {
+            return None;
+        }
+
+        let other = self.target.value != selected && self.target.value < count;
+
+        Some(
+            container(
+                column![
+                    row![
+                        text(format!("Record {}", selected)),
+                        button("Copy").on_press(RecordToolsMessage::Copy),
+                        button("Paste").on_press(RecordToolsMessage::Paste),
+                        button("Clear").on_press(RecordToolsMessage::Clear),
+                        button("Close").on_press(RecordToolsMessage::Close),
+                    ]
+                    .spacing(6)
+                    .align_y(Alignment::Center),
+                    row![
+                        text("Other record:"),
+                        self.target
+                            .view(0, 0, count, 1, RecordToolsMessage::Target, None),
+                        button("Duplicate To")
+                            .on_press_maybe(other.then_some(RecordToolsMessage::Duplicate)),
+                        button("Swap With")
+                            .on_press_maybe(other.then_some(RecordToolsMessage::Swap)),
+                    ]
+                    .spacing(6)
+                    .align_y(Alignment::Center),
+                ]
+                .push_maybe(self.notice.as_deref().map(text))
+                .spacing(6),
+            )
+            .style(container::rounded_box)
+            .padding(10)
+            .into(),
+        )
+    }
+}
@@ -0,0 +1,160 @@
+use crate::*;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, row, text},
+    Element,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenumberMessage {
+    Show,
+    Close,
+    Input((usize, NumInputMessage<usize>)),
+    Move,
+    Swap,
+    Apply,
+    Cancel,
+}
+
+/// Renumber the editor was asked to work out or apply.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenumberRequest {
+    /// Moves the records `from..=through` so the first ends up at `to`.
+    Move {
+        from: usize,
+        through: usize,
+        to: usize,
+    },
+    Swap(usize, usize),
+    /// Saves the renumber shown within the preview.
+    Apply,
+}
+
+/// Inputs to move or swap records, followed by a preview of the changes
+/// that have to be applied before anything is saved.
+#[derive(Debug)]
+pub struct RenumberPicker {
+    pub show: bool,
+    pub from: NumInput<usize, RenumberMessage>,
+    pub through: NumInput<usize, RenumberMessage>,
+    pub to: NumInput<usize, RenumberMessage>,
+    /// Changes of the worked out renumber, set by the editor.
+    pub preview: Option<Vec<String>>,
+    pub error: Option<String>,
+}
+
+impl Default for RenumberPicker {
+    fn default() -> Self {
+        Self {
+            show: false,
+            from: NumInput::new(0),
+            through: NumInput::new(0),
+            to: NumInput::new(0),
+            preview: None,
+            error: None,
+        }
+    }
+}
+
+impl RenumberPicker {
+    /// Opens the picker with the given record as the one to move.
+    pub fn open(&mut self, id: usize) {
+        self.show = true;
+        self.from.value = id;
+        self.through.value = id;
+        self.to.value = id;
+        self.preview = None;
+        self.error = None;
+    }
+
+    pub fn update(&mut self, msg: RenumberMessage) -> Option<RenumberRequest> {
+        match msg {
+            RenumberMessage::Show => self.show = true,
+            RenumberMessage::Close => {
+                self.show = false;
+                self.preview = None;
+            }
+            RenumberMessage::Input((id, value)) => {
+                let value = value.get_data();
+
+                match id {
+                    0 => {
+                        self.from.value = value;
+                        self.through.value = self.through.value.max(value);
+                    }
+                    1 => {
+                        self.through.value = value;
+                        self.from.value = self.from.value.min(value);
+                    }
+                    _ => self.to.value = value,
+                }
+
+                self.preview = None;
+            }
+            RenumberMessage::Move => {
+                return Some(RenumberRequest::Move {
+                    from: self.from.value,
+                    through: self.through.value,
+                    to: self.to.value,
+                });
+            }
+            RenumberMessage::Swap => {
+                return Some(RenumberRequest::Swap(self.from.value, self.to.value));
+            }
+            RenumberMessage::Apply => return Some(RenumberRequest::Apply),
+            RenumberMessage::Cancel => self.preview = None,
+        }
+
+        None
+    }
+
+    /// Shows the picker for `count` records.
+    pub fn view(&self, count: usize) -> Option<Element<'_, RenumberMessage>> {
+        if !self.show {
+            return None;
+        }
+
+        let inputs = row![
+            text("Records"),
+            self.from.view(0, 0, count, 1, RenumberMessage::Input, None),
+            text("through"),
+            self.through
+                .view(1, 0, count, 1, RenumberMessage::Input, None),
+            text("to"),
+            self.to.view(2, 0, count, 1, RenumberMessage::Input, None),
+            button("Move").on_press(RenumberMessage::Move),
+            button("Swap").on_press(RenumberMessage::Swap),
+            button("Close").on_press(RenumberMessage::Close),
+        ]
+        .spacing(6)
+        .align_y(Alignment::Center);
+
+        let mut col = column![inputs].spacing(6);
+
+        if let Some(preview) = &self.preview {
+            let rows = preview.iter().map(ActionRow::new).collect();
+
+            col = col
+                .push(action_list(
+                    &format!("{} changes, nothing is saved until applied", preview.len()),
+                    rows,
+                    RenumberMessage::Cancel,
+                ))
+                .push(
+                    row![
+                        button("Apply").on_press_maybe(
+                            (!preview.is_empty()).then_some(RenumberMessage::Apply)
+                        ),
+                        button("Cancel").on_press(RenumberMessage::Cancel),
+                    ]
+                    .spacing(6),
+                );
+        }
+
+        if let Some(error) = &self.error {
+            col = col.push(text(error.clone()));
+        }
+
+        Some(col.into())
+    }
+}
@@ -0,0 +1,168 @@
+use crate::*;
+use iced::{
+    alignment::Alignment,
+    widget::{button, column, container, row, scrollable, text, Column, Image, Row, Space},
+    Element, Length,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+const COLUMNS: usize = 8;
+const THUMB_SIZE: f32 = 64.0;
+const CELL_WIDTH: f32 = 96.0;
+const ROW_HEIGHT: f32 = 116.0;
+/// Rows built past either edge of the visible part of the gallery.
+const OVERSCAN: usize = 2;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GalleryMessage {
+    Close,
+    Scrolled { offset: f32, height: f32 },
+    Pick(i64),
+}
+
+/// Thumbnails of every item or npc sprite within the resources folder, to
+/// pick a sprite by sight. Sprites other records use are marked. Like the
+/// record list, only the rows within view are built.
+#[derive(Debug, Default)]
+pub struct SpriteGallery {
+    pub show: bool,
+    pub kind: PickerKind,
+    resources: PathBuf,
+    sprites: Vec<i64>,
+    /// How many other records use each sprite.
+    used: HashMap<i64, usize>,
+    offset: f32,
+    height: f32,
+}
+
+impl SpriteGallery {
+    pub fn new(kind: PickerKind) -> Self {
+        Self {
+            kind,
+            height: 600.0,
+            ..Default::default()
+        }
+    }
+
+    /// Scans the resources folder and opens the gallery. `used` holds the
+    /// sprite of every named record besides the one the sprite is picked for,
+    /// unnamed records are unused slots still on their default sprite.
+    pub fn open(&mut self, resources: &Path, used: impl IntoIterator<Item = i64>) {
+        self.show = true;
+        self.resources = resources.to_path_buf();
+        self.sprites = self.kind.sprite_ids(resources);
+        self.used.clear();
+
+        for sprite in used {
+            *self.used.entry(sprite).or_default() += 1;
+        }
+    }
+
+    /// Returns the sprite that was picked, closing the gallery.
+    pub fn update(&mut self, msg: GalleryMessage) -> Option<i64> {
+        match msg {
+            GalleryMessage::Close => self.show = false,
+            GalleryMessage::Scrolled { offset, height } => {
+                self.offset = offset;
+                self.height = height;
+            }
+            GalleryMessage::Pick(sprite) => {
+                self.show = false;
+                return Some(sprite);
+            }
+        }
+
+        None
+    }
+
+    fn cell(&self, sprite: i64, selected: i64) -> Element<'_, GalleryMessage> {
+        let used = self.used.get(&sprite).copied().unwrap_or(0);
+        let style = match (sprite == selected, used > 0) {
+            (true, _) => button::primary,
+            (false, true) => button::secondary,
+            (false, false) => button::text,
+        };
+
+        button(
+            column![
+                Image::new(self.kind.sprite_path(&self.resources, sprite))
+                    .width(Length::Fixed(THUMB_SIZE))
+                    .height(Length::Fixed(THUMB_SIZE)),
+                text(sprite.to_string()),
+                text(match used {
+                    0 => String::new(),
+                    used => format!("Used x{}", used),
+                })
+                .size(12),
+            ]
+            .align_x(Alignment::Center),
+        )
+        .on_press(GalleryMessage::Pick(sprite))
+        .style(style)
+        .width(Length::Fixed(CELL_WIDTH))
+        .height(Length::Fixed(ROW_HEIGHT))
+        .into()
+    }
+
+    /// The gallery while open. `selected` is the sprite the record has now.
+    pub fn view(&self, selected: i64) -> Option<Element<'_, GalleryMessage>> {
+        if !self.show {
+            return None;
+        }
+
+        let rows = self.sprites.len().div_ceil(COLUMNS);
+        let first = ((self.offset / ROW_HEIGHT) as usize)
+            .saturating_sub(OVERSCAN)
+            .min(rows);
+        let last = (first + (self.height / ROW_HEIGHT).ceil() as usize + OVERSCAN * 2).min(rows);
+
+        let grid = self.sprites[first * COLUMNS..(last * COLUMNS).min(self.sprites.len())]
+            .chunks(COLUMNS)
+            .fold(Column::new(), |grid, chunk| {
+                grid.push(chunk.iter().fold(Row::new(), |line, sprite| {
+                    line.push(self.cell(*sprite, selected))
+                }))
+            });
+
+        let body = column![
+            Space::with_height(Length::Fixed(first as f32 * ROW_HEIGHT)),
+            grid,
+            Space::with_height(Length::Fixed((rows - last) as f32 * ROW_HEIGHT)),
+        ];
+
+        let title = match self.sprites.len() {
+            0 => format!(
+                "No sprites found within {}",
+                self.kind.sprite_path(&self.resources, 0).display()
+            ),
+            count => format!("{} sprites, marked ones are used by other records", count),
+        };
+
+        Some(
+            container(
+                column![
+                    row![
+                        text(title).width(Length::Fill),
+                        button("Close").on_press(GalleryMessage::Close),
+                    ]
+                    .align_y(Alignment::Center),
+                    scrollable(body)
+                        .on_scroll(|viewport| GalleryMessage::Scrolled {
+                            offset: viewport.absolute_offset().y,
+                            height: viewport.bounds().height,
+                        })
+                        .height(Length::Fill),
+                ]
+                .spacing(6),
+            )
+            .style(container::rounded_box)
+            .padding(10)
+            .width(Length::Fixed(COLUMNS as f32 * CELL_WIDTH + 40.0))
+            .height(Length::Fixed(480.0))
+            .into(),
+        )
+    }
+}
@@ -0,0 +1,60 @@
+use ascending_data::*;
+use ascending_logger::warn;
+use serde::{Deserialize, Serialize};
+use std::{fs::OpenOptions, io::BufReader, path::Path};
+
+/// Settings of an editor, kept next to it within a file such as
+/// `item_config.json`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EditorConfig {
+    pub save_json: bool,
+    /// Whether records are read from their bin files or json copies.
+    pub source: DataSource,
+    pub project: ProjectPaths,
+    pub recent_projects: Vec<ProjectPaths>,
+}
+
+impl EditorConfig {
+    /// Json copies are always written when they are the source of truth.
+    pub fn save_json(&self) -> bool {
+        self.save_json || self.source == DataSource::Json
+    }
+
+    /// Reads the config file, creating it with the defaults if there is none.
+    pub fn load(name: &str) -> Self {
+        if !Path::new(name).exists() {
+            let data = Self::default();
+
+            if let Err(e) = data.save(name) {
+                warn!("Could not create {}, err {}", name, e);
+            }
+
+            return data;
+        }
+
+        match OpenOptions::new().read(true).open(name) {
+            Ok(file) => match serde_json::from_reader(BufReader::new(file)) {
+                Ok(data) => data,
+                Err(e) => {
+                    warn!("Could not read {}, err {:?}", name, e);
+                    Self::default()
+                }
+            },
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self, name: &str) -> Result<(), String> {
+        match OpenOptions::new()
+            .truncate(true)
+            .write(true)
+            .create(true)
+            .open(name)
+        {
+            Ok(file) => serde_json::to_writer_pretty(&file, self)
+                .map_err(|e| format!("Serdes File Error Err {:?}", e)),
+            Err(e) => Err(format!("Failed to open {}, Err {:?}", name, e)),
+        }
+    }
+}
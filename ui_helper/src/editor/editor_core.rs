@@ -0,0 +1,1214 @@
+use crate::*;
+use ascending_data::*;
+use ascending_logger::warn;
+use iced::{
+    clipboard,
+    widget::{button, row, text, Column},
+    window, Element, Task,
+};
+use std::sync::{Arc, Mutex};
+
+/// Optional tools an editor offers within its menu.
+#[derive(Copy, Clone, Debug)]
+pub struct EditorTools {
+    /// The sprite audit, for records with a sprite.
+    pub sprites: bool,
+    /// Used By and Renumber, for records other records point at.
+    pub referenced: bool,
+}
+
+/// Records of one kind opened within an editor, along with everything the
+/// editors share: saving, backups, projects, history, unsaved tracking,
+/// crash recovery, references, renumbering, record tools and bulk edits.
+///
+/// The editor keeps its form and filters. It hands `EditorMessage`s to
+/// `update` and shows the current record again whenever `take_refresh`
+/// says the record changed underneath the form.
+pub struct EditorCore<T: SetRecord> {
+    pub data: Vec<(T, bool)>,
+    pub list: Vec<ListData>,
+    pub records: RecordList,
+    pub currentid: usize,
+    pub config: EditorConfig,
+    /// File the config is saved to once another project is opened.
+    config_file: &'static str,
+    pub project_config: ProjectConfig,
+    history: History<T>,
+    /// Unsaved records, shared with the panic hook so it can write them.
+    journal: Arc<Mutex<Journal<T>>>,
+    tools: EditorTools,
+    load_report: LoadReport,
+    backups: Option<Vec<Backup>>,
+    /// Why the last backup could not be restored, shown atop the backups.
+    backup_error: Option<String>,
+    /// Records that could not be saved, shown until dismissed.
+    save_errors: Vec<String>,
+    projects: ProjectPicker,
+    close_request: Option<window::Id>,
+    show_unsaved: bool,
+    references: Option<Vec<ReferenceIssue>>,
+    sprite_audit: Option<AssetAudit>,
+    used_by: Option<ReverseIndex>,
+    renumber: RenumberPicker,
+    renumber_plan: Option<RenumberPlan>,
+    record_tools: RecordTools,
+    bulk_editor: BulkEditor,
+    bulk_plan: Option<BulkPlan<T>>,
+    recovery: Option<JournalFile<T>>,
+    /// Whether the form has to show the current record again.
+    refresh: bool,
+    /// Whether the listed records have to be worked out again, as the
+    /// records, the search or the filters changed.
+    relist: bool,
+}
+
+impl<T: SetRecord> EditorCore<T> {
+    /// An editor without records, `load_project` reads them.
+    pub fn new(
+        config: EditorConfig,
+        config_file: &'static str,
+        journal: Arc<Mutex<Journal<T>>>,
+        tools: EditorTools,
+    ) -> Self {
+        Self {
+            data: Vec::with_capacity(T::MAX),
+            list: Vec::with_capacity(T::MAX),
+            records: RecordList::markable(),
+            currentid: 0,
+            config,
+            config_file,
+            project_config: ProjectConfig::default(),
+            history: History::default(),
+            journal,
+            tools,
+            load_report: LoadReport::default(),
+            backups: None,
+            backup_error: None,
+            save_errors: Vec::new(),
+            projects: ProjectPicker::default(),
+            close_request: None,
+            show_unsaved: false,
+            references: None,
+            sprite_audit: None,
+            used_by: None,
+            renumber: RenumberPicker::default(),
+            renumber_plan: None,
+            record_tools: RecordTools::default(),
+            bulk_editor: BulkEditor {
+                fields: bulk_fields::<T>(),
+                ..Default::default()
+            },
+            bulk_plan: None,
+            recovery: None,
+            refresh: false,
+            relist: true,
+        }
+    }
+
+    /// Name of the record kind within messages, such as `item`.
+    fn noun() -> String {
+        T::NAME.to_lowercase()
+    }
+
+    pub fn current(&self) -> &T {
+        &self.data[self.currentid].0
+    }
+
+    pub fn current_mut(&mut self) -> &mut T {
+        &mut self.data[self.currentid].0
+    }
+
+    /// Whether the form has to show the current record again, clearing the
+    /// request.
+    pub fn take_refresh(&mut self) -> bool {
+        std::mem::take(&mut self.refresh)
+    }
+
+    /// Has the records listed again on the next `refresh_records`, for the
+    /// editor to call once its filters changed.
+    pub fn filters_changed(&mut self) {
+        self.relist = true;
+    }
+
+    pub fn unsaved(&self) -> usize {
+        self.data.iter().filter(|data| data.1).count()
+    }
+
+    /// Records a change the form of the editor made to a record, given the
+    /// record as it was before. Only actual changes are dirty, selecting
+    /// slots and such is not.
+    pub fn edited(&mut self, id: usize, before: T, merge: bool) {
+        if self.data[id].0 != before {
+            self.remember_saved(id, &before);
+            self.history.push(id, before, merge);
+            self.data[id].1 = true;
+            self.relist = true;
+        }
+    }
+
+    pub fn update(
+        &mut self,
+        msg: EditorMessage,
+        extras: impl FnOnce(&EditorConfig, &ProjectConfig) -> Result<(), String>,
+    ) -> Task<EditorMessage> {
+        let id = self.currentid;
+
+        match msg {
+            EditorMessage::CloseRequested(window) => {
+                if self.unsaved() == 0 {
+                    return window::close(window);
+                }
+
+                self.close_request = Some(window);
+            }
+            EditorMessage::SaveAndClose => {
+                self.save_all();
+
+                return match self.close_request {
+                    Some(window) if self.unsaved() == 0 => window::close(window),
+                    _ => Task::none(),
+                };
+            }
+            EditorMessage::Autosave => {
+                if let Ok(mut journal) = self.journal.lock() {
+                    if let Err(e) = journal.write() {
+                        warn!("Could not write the recovery journal, err {}", e);
+                    }
+                }
+            }
+            EditorMessage::RestoreRecovery => self.restore_recovery(),
+            EditorMessage::DiscardRecovery => {
+                self.recovery = None;
+
+                if let Err(e) = Journal::<T>::discard_previous(&self.config.project.data) {
+                    warn!("Could not remove the previous recovery journal, err {}", e);
+                }
+            }
+            EditorMessage::DiscardAndClose => {
+                if let Err(e) = Journal::<T>::discard(&self.config.project.data) {
+                    warn!("Could not remove the recovery journal, err {}", e);
+                }
+
+                return self
+                    .close_request
+                    .take()
+                    .map_or_else(Task::none, window::close);
+            }
+            EditorMessage::CancelClose => self.close_request = None,
+            EditorMessage::CheckReferences => {
+                self.references = Some(self.data_set().check_references());
+            }
+            EditorMessage::CloseReferences => self.references = None,
+            EditorMessage::AuditSprites => {
+                self.sprite_audit = Some(AssetAudit::new(
+                    &self.config.project.resources,
+                    &self.project_config,
+                    &self.data_set(),
+                ));
+            }
+            EditorMessage::CloseSpriteAudit => self.sprite_audit = None,
+            EditorMessage::ShowUsedBy => {
+                self.used_by = Some(ReverseIndex::new(&self.data_set()));
+            }
+            EditorMessage::CloseUsedBy => self.used_by = None,
+            EditorMessage::Renumber(RenumberMessage::Show) => {
+                self.renumber.open(self.currentid);
+                self.renumber_plan = None;
+            }
+            EditorMessage::Renumber(msg) => {
+                match self.renumber.update(msg) {
+                    Some(RenumberRequest::Apply) => {
+                        if let Err(e) = self.apply_renumber(extras) {
+                            self.renumber.error = Some(e);
+                        }
+                    }
+                    Some(request) => match self.plan_renumber(request) {
+                        Ok(plan) => {
+                            self.renumber.preview =
+                                Some(plan.changes.iter().map(ToString::to_string).collect());
+                            self.renumber.error = None;
+                            self.renumber_plan = Some(plan);
+                        }
+                        Err(e) => {
+                            self.renumber.preview = None;
+                            self.renumber.error = Some(e);
+                        }
+                    },
+                    None => {}
+                }
+
+                if self.renumber.preview.is_none() {
+                    self.renumber_plan = None;
+                }
+            }
+            EditorMessage::GoToRecord(id) => {
+                if id < self.data.len() {
+                    self.select(id);
+                }
+            }
+            EditorMessage::ShowUnsaved => self.show_unsaved = true,
+            EditorMessage::CloseUnsaved => self.show_unsaved = false,
+            EditorMessage::SaveRecord(id) => self.save_record_or_report(id),
+            EditorMessage::DiscardRecord(id) => {
+                if let Err(e) = self.discard_record(id) {
+                    warn!("Could not discard {} {}, err {}", Self::noun(), id, e);
+                }
+            }
+            EditorMessage::History(action) => self.apply_history(action),
+            EditorMessage::SaveAllButtonPress => self.save_all(),
+            EditorMessage::SaveButtonPress => self.save_record_or_report(id),
+            EditorMessage::RevertButtonPress => {
+                if let Err(e) = self.discard_record(id) {
+                    warn!("Could not revert {} {}, err {}", Self::noun(), id, e);
+                }
+            }
+            EditorMessage::RecordTools(msg) => {
+                let noun = Self::noun();
+
+                match self.record_tools.update(msg) {
+                    Some(RecordToolsRequest::Copy) => match copy_record(&self.data[id].0) {
+                        Ok(text) => {
+                            self.record_tools.notice = Some(format!("Copied {} {}", noun, id));
+                            return clipboard::write(text);
+                        }
+                        Err(e) => self.record_tools.notice = Some(e),
+                    },
+                    Some(RecordToolsRequest::Paste) => {
+                        return clipboard::read().map(EditorMessage::PasteRecord);
+                    }
+                    Some(RecordToolsRequest::Duplicate(target)) if target < self.data.len() => {
+                        self.replace_record(target, self.data[id].0.clone());
+                        self.select(target);
+                        self.record_tools.target.value = (target + 1).min(self.data.len() - 1);
+                        self.record_tools.notice =
+                            Some(format!("Duplicated {} {} to {}", noun, id, target));
+                    }
+                    Some(RecordToolsRequest::Swap(target)) if target < self.data.len() => {
+                        self.swap_records(id, target);
+                        self.refresh = true;
+                        self.record_tools.notice =
+                            Some(format!("Swapped {} {} and {}", noun, id, target));
+                    }
+                    Some(RecordToolsRequest::Clear) => {
+                        self.replace_record(id, T::default());
+                        self.refresh = true;
+                    }
+                    _ => {}
+                }
+            }
+            EditorMessage::PasteRecord(text) => {
+                match paste_record::<T>(text.as_deref().unwrap_or_default()) {
+                    Ok(data) => {
+                        self.replace_record(id, data);
+                        self.refresh = true;
+                        self.record_tools.notice =
+                            Some(format!("Pasted into {} {}", Self::noun(), id));
+                    }
+                    Err(e) => self.record_tools.notice = Some(e),
+                }
+            }
+            EditorMessage::BulkEdit(msg) => match self.bulk_editor.update(msg) {
+                Some(BulkEditRequest::Preview { field, mode, input }) => {
+                    self.plan_bulk_edit(&field, mode, &input)
+                }
+                Some(BulkEditRequest::Apply) => self.apply_bulk_edit(),
+                None if self.bulk_editor.preview.is_none() => self.bulk_plan = None,
+                None => {}
+            },
+            EditorMessage::RecordList(RecordListMessage::Select(id)) => self.select(id),
+            EditorMessage::RecordList(msg) => {
+                self.relist |= matches!(msg, RecordListMessage::Search(_));
+                return self.records.update(msg).map(EditorMessage::RecordList);
+            }
+            EditorMessage::AddRecord => {
+                if let Err(e) = self.add_record() {
+                    warn!("Could not add {}, err {}", Self::noun(), e);
+                }
+            }
+            EditorMessage::DeleteLastRecord => {
+                if let Err(e) = self.delete_last_record() {
+                    warn!("Could not delete {}, err {}", Self::noun(), e);
+                }
+            }
+            EditorMessage::DismissLoadReport => self.load_report.issues.clear(),
+            EditorMessage::DismissSaveErrors => self.save_errors.clear(),
+            EditorMessage::ResolveConflict(keep) => self.resolve_conflicts(keep, 1),
+            EditorMessage::ResolveAllConflicts(keep) => {
+                self.resolve_conflicts(keep, self.load_report.conflicts.len());
+            }
+            EditorMessage::Project(msg) => {
+                let project = match self.projects.update(msg) {
+                    Some(ProjectRequest::Recent(index)) => {
+                        self.config.recent_projects.get(index).cloned()
+                    }
+                    Some(ProjectRequest::Folders { data, resources }) => {
+                        Some(ProjectPaths::new(data, resources))
+                    }
+                    None => None,
+                };
+
+                if let Some(project) = project {
+                    match self.open_project(project, extras) {
+                        Ok(()) => self.projects.show = false,
+                        Err(e) => self.projects.error = Some(e),
+                    }
+                }
+            }
+            EditorMessage::ShowBackups => {
+                self.backups = Some(T::backups(&self.config.project.data, id));
+                self.backup_error = None;
+            }
+            EditorMessage::CloseBackups => {
+                self.backups = None;
+                self.backup_error = None;
+            }
+            EditorMessage::RestoreBackup(index) => {
+                let Some(backup) = self.backups.as_ref().and_then(|list| list.get(index)) else {
+                    return Task::none();
+                };
+
+                match T::load_backup(backup) {
+                    Ok(data) => {
+                        self.backup_error = None;
+                        self.replace_record(id, data);
+                        self.refresh = true;
+                    }
+                    Err(e) => {
+                        let e = format!("Could not restore {}, err {}", backup.label(), e);
+
+                        warn!("{}", e);
+                        self.backup_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        Task::none()
+    }
+
+    /// Loads the records of the current project, replacing the loaded ones.
+    /// `extras` reads whatever else the editor needs of the project, it is
+    /// only called once the records could be read.
+    pub fn load_project(
+        &mut self,
+        extras: impl FnOnce(&EditorConfig, &ProjectConfig) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let project_config = ProjectConfig::load(&self.config.project.data)?;
+        let (data, load_report) = T::load_files(
+            &self.config.project.data,
+            self.config.source,
+            self.config.save_json(),
+        )?;
+
+        extras(&self.config, &project_config)?;
+
+        for issue in &load_report.issues {
+            warn!("{}", issue);
+        }
+
+        for conflict in &load_report.conflicts {
+            warn!("{}", conflict);
+        }
+
+        self.list = data
+            .iter()
+            .enumerate()
+            .map(|(i, (data, _))| ListData::new(i, data.name().to_owned()))
+            .collect();
+        self.data = data;
+        self.load_report = load_report;
+        self.project_config = project_config;
+        self.references = None;
+        self.used_by = None;
+        self.sprite_audit = None;
+        self.history.clear();
+        self.records.marked.clear();
+        self.bulk_plan = None;
+        self.bulk_editor.preview = None;
+
+        if let Ok(mut journal) = self.journal.lock() {
+            journal.set_root(&self.config.project.data);
+        }
+
+        self.recovery = match Journal::take_previous(&self.config.project.data) {
+            Ok(recovery) => recovery.filter(|file| !file.records.is_empty()),
+            Err(e) => {
+                warn!("Could not read the recovery journal, err {}", e);
+                None
+            }
+        };
+        self.backups = None;
+        self.currentid = 0;
+        self.refresh = true;
+        self.relist = true;
+        Ok(())
+    }
+
+    pub fn select(&mut self, id: usize) {
+        self.history.seal();
+        self.currentid = id;
+        self.refresh = true;
+        self.refresh_backups();
+    }
+
+    fn apply_history(&mut self, action: HistoryMessage) {
+        let id = match action {
+            HistoryMessage::Undo => self.history.undo(&mut self.data),
+            HistoryMessage::Redo => self.history.redo(&mut self.data),
+        };
+
+        if let Some(id) = id {
+            self.relist = true;
+            self.select(id);
+        }
+    }
+
+    /// Replaces a record as a single change of the history, the same as
+    /// editing it by hand.
+    fn replace_record(&mut self, id: usize, data: T) {
+        let before = std::mem::replace(&mut self.data[id].0, data);
+
+        if self.data[id].0 != before {
+            self.remember_saved(id, &before);
+            self.history.seal();
+            self.history.push(id, before, false);
+            self.data[id].1 = true;
+            self.relist = true;
+        }
+    }
+
+    /// Swaps two records as a single change of the history.
+    fn swap_records(&mut self, a: usize, b: usize) {
+        if a == b || self.data[a].0 == self.data[b].0 {
+            return;
+        }
+
+        let (before_a, before_b) = (self.data[a].0.clone(), self.data[b].0.clone());
+
+        self.remember_saved(a, &before_a);
+        self.remember_saved(b, &before_b);
+        self.history.seal();
+        self.history.push_group(vec![(a, before_a), (b, before_b)]);
+
+        self.data.swap(a, b);
+        self.data[a].1 = true;
+        self.data[b].1 = true;
+        self.relist = true;
+    }
+
+    /// Works out a bulk edit of the marked records, showing the changes as a
+    /// preview to apply.
+    fn plan_bulk_edit(&mut self, field: &str, mode: BulkMode, input: &str) {
+        self.bulk_editor.preview = None;
+        self.bulk_plan = None;
+
+        let op = match mode {
+            BulkMode::Set => BulkOp::set(input),
+            BulkMode::Expression => BulkOp::expression(input),
+        };
+
+        let op = match op {
+            Ok(op) => op,
+            Err(e) => {
+                self.bulk_editor.notice = Some(e);
+                return;
+            }
+        };
+
+        let plan = BulkPlan::new(
+            field,
+            &op,
+            self.records
+                .marked
+                .iter()
+                .filter_map(|&id| self.data.get(id).map(|(data, _)| (id, data))),
+        );
+
+        let mut preview: Vec<String> = plan
+            .changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{} {} {}: {} {} -> {}",
+                    T::NAME,
+                    change.id,
+                    change.before.name(),
+                    plan.field,
+                    change.old,
+                    change.new
+                )
+            })
+            .collect();
+        preview.extend(plan.errors.iter().map(|e| format!("Skipped {}", e)));
+
+        self.bulk_editor.notice = Some(format!(
+            "{} of {} marked records change",
+            plan.changes.len(),
+            self.records.marked.len()
+        ));
+        self.bulk_editor.preview = Some(preview);
+        self.bulk_plan = Some(plan);
+    }
+
+    /// Makes the changes of the previewed bulk edit as unsaved changes, undone
+    /// as one step, unless one of the records was changed since the preview.
+    fn apply_bulk_edit(&mut self) {
+        let Some(plan) = self.bulk_plan.take() else {
+            return;
+        };
+
+        self.bulk_editor.preview = None;
+
+        if !plan.is_current(&self.data) {
+            self.bulk_editor.notice =
+                Some("Records were changed since the preview, preview again.".into());
+            return;
+        }
+
+        let changed = plan.changes.len();
+        let mut before = Vec::with_capacity(changed);
+
+        for change in plan.changes {
+            self.remember_saved(change.id, &change.before);
+
+            let record = &mut self.data[change.id];
+
+            before.push((change.id, std::mem::replace(&mut record.0, change.after)));
+            record.1 = true;
+        }
+
+        self.history.seal();
+        self.history.push_group(before);
+        self.refresh = true;
+        self.relist = true;
+        self.bulk_editor.notice = Some(format!("Changed {} records", changed));
+    }
+
+    /// Hands the history the state of a clean record about to change, which
+    /// is the state saved on disk.
+    fn remember_saved(&mut self, id: usize, before: &T) {
+        if !self.data[id].1 {
+            self.history.mark_saved(id, before.clone());
+        }
+    }
+
+    /// Mirrors the names and dirty flags into the record list, then lists
+    /// the records passing its search and the filters of the editor. Does
+    /// nothing unless the records, the search or the filters changed since.
+    pub fn refresh_records(&mut self, filter: impl Fn(&T) -> bool) {
+        if !std::mem::take(&mut self.relist) {
+            return;
+        }
+
+        for (entry, (data, dirty)) in self.list.iter_mut().zip(&self.data) {
+            if entry.name != data.name() {
+                entry.name = data.name().to_owned();
+            }
+
+            entry.dirty = *dirty;
+        }
+
+        let data = &self.data;
+
+        self.records.refresh(&self.list, |id| {
+            data.get(id).is_some_and(|(data, _)| filter(data))
+        });
+    }
+
+    /// Saves every unsaved record, listing the ones that failed above the
+    /// editor.
+    pub fn save_all(&mut self) {
+        let unsaved: Vec<usize> = (0..self.data.len()).filter(|&id| self.data[id].1).collect();
+
+        self.save_errors = T::save_all(
+            &self.config.project.data,
+            &mut self.data,
+            self.config.save_json(),
+        );
+
+        self.relist = true;
+
+        for id in unsaved {
+            if !self.data[id].1 {
+                self.history.mark_saved(id, self.data[id].0.clone());
+            }
+        }
+
+        for e in &self.save_errors {
+            warn!("{}", e);
+        }
+    }
+
+    fn save_record_or_report(&mut self, id: usize) {
+        if let Err(e) = self.save_record(id) {
+            let e = format!("Could not save {} {}, err {}", T::NAME, id, e);
+
+            warn!("{}", e);
+            self.save_errors.push(e);
+        }
+    }
+
+    /// Every record of the project, with the unsaved state of the records
+    /// open within this editor.
+    pub fn data_set(&self) -> DataSet {
+        let mut set = DataSet::load(&self.config.project.data, self.config.source);
+        *T::of_set_mut(&mut set) = self.data.iter().map(|(data, _)| data.clone()).collect();
+        set
+    }
+
+    /// Works out a renumber of the records against the saved records of
+    /// every kind, so it can be previewed before anything is written.
+    fn plan_renumber(&self, request: RenumberRequest) -> Result<RenumberPlan, String> {
+        if self.unsaved() > 0 {
+            return Err(format!(
+                "Save or discard the {} unsaved records first",
+                self.unsaved()
+            ));
+        }
+
+        let count = self.data.len();
+        let renumber = match request {
+            RenumberRequest::Move { from, through, to } => {
+                Renumber::moved(T::KIND, count, from, through, to)?
+            }
+            RenumberRequest::Swap(a, b) => Renumber::swapped(T::KIND, count, a, b)?,
+            RenumberRequest::Apply => return Err("Nothing to apply".into()),
+        };
+
+        RenumberPlan::new(
+            DataSet::load_checked(&self.config.project.data, self.config.source)?,
+            renumber,
+        )
+    }
+
+    /// Saves the previewed renumber and reloads the records under their new
+    /// ids.
+    fn apply_renumber(
+        &mut self,
+        extras: impl FnOnce(&EditorConfig, &ProjectConfig) -> Result<(), String>,
+    ) -> Result<(), String> {
+        let Some(plan) = self.renumber_plan.take() else {
+            return Err("Nothing to apply".into());
+        };
+
+        self.renumber.preview = None;
+
+        if self.unsaved() > 0 || !plan.is_current(&self.config.project.data, self.config.source)? {
+            return Err("Records changed since the preview, work it out again".into());
+        }
+
+        plan.apply(&self.config.project.data, self.config.save_json())?;
+        self.renumber.show = false;
+
+        let id = plan.renumber.new_id(self.currentid);
+        self.load_project(extras)?;
+        self.select(id);
+        Ok(())
+    }
+
+    /// Mirrors the unsaved records into the recovery journal.
+    pub fn track_unsaved(&mut self) {
+        if let Ok(mut journal) = self.journal.lock() {
+            if let Err(e) = journal.track(&self.data) {
+                warn!("Could not update the recovery journal, err {}", e);
+            }
+        }
+    }
+
+    /// Puts the records of the recovery journal back as unsaved changes.
+    fn restore_recovery(&mut self) {
+        let Some(recovery) = self.recovery.take() else {
+            return;
+        };
+
+        for (id, data) in recovery.records {
+            if id >= self.data.len() {
+                warn!(
+                    "Skipped recovered {} {}, it no longer exists",
+                    Self::noun(),
+                    id
+                );
+                continue;
+            }
+
+            self.data[id] = (data, true);
+        }
+
+        self.refresh = true;
+        self.relist = true;
+
+        // The restored records are only safe to drop from the previous
+        // journal once they are within the journal of this session.
+        self.track_unsaved();
+
+        let written = match self.journal.lock() {
+            Ok(mut journal) => journal.write(),
+            Err(_) => Err("the journal is poisoned".into()),
+        };
+
+        if let Err(e) =
+            written.and_then(|()| Journal::<T>::discard_previous(&self.config.project.data))
+        {
+            warn!("Could not replace the previous recovery journal, err {}", e);
+        }
+    }
+
+    fn save_record(&mut self, id: usize) -> Result<(), String> {
+        self.data[id]
+            .0
+            .save(&self.config.project.data, id, self.config.save_json())?;
+        self.data[id].1 = false;
+        self.history.mark_saved(id, self.data[id].0.clone());
+        self.relist = true;
+        self.refresh_backups();
+        Ok(())
+    }
+
+    /// Drops the unsaved changes of a record by reading it back from disk,
+    /// along with its history.
+    fn discard_record(&mut self, id: usize) -> Result<(), String> {
+        let data = T::load_record(&self.config.project.data, id, self.config.source)?;
+
+        self.data[id] = (data, false);
+        self.history.forget(id);
+        self.refresh |= id == self.currentid;
+        self.relist = true;
+        Ok(())
+    }
+
+    fn can_add(&self) -> bool {
+        self.data.len() < self.project_config.limit::<T>()
+    }
+
+    /// Appends a new default record after the last one and selects it.
+    fn add_record(&mut self) -> Result<(), String> {
+        if !self.can_add() {
+            return Err(format!(
+                "the project allows at most {} {}s",
+                self.project_config.limit::<T>(),
+                Self::noun()
+            ));
+        }
+
+        let id = self.data.len();
+        let data = T::default();
+
+        data.save(&self.config.project.data, id, self.config.save_json())?;
+        self.list.push(ListData::new(id, data.name().to_owned()));
+        self.data.push((data, false));
+        self.relist = true;
+        self.select(id);
+        Ok(())
+    }
+
+    /// Removes the last record along with its files. Only records at the end
+    /// can be removed so the ids of all others stay the same.
+    fn delete_last_record(&mut self) -> Result<(), String> {
+        let id = self.data.len() - 1;
+
+        if id == 0 {
+            return Err("the only remaining record can not be deleted".into());
+        }
+
+        T::delete_files(&self.config.project.data, id)?;
+        self.history.forget(id);
+        self.data.pop();
+        self.list.pop();
+        self.relist = true;
+
+        if self.currentid >= id {
+            self.select(id - 1);
+        }
+
+        Ok(())
+    }
+
+    fn open_project(
+        &mut self,
+        project: ProjectPaths,
+        extras: impl FnOnce(&EditorConfig, &ProjectConfig) -> Result<(), String>,
+    ) -> Result<(), String> {
+        if self.unsaved() > 0 {
+            return Err(format!(
+                "Save or discard the {} unsaved records first",
+                self.unsaved()
+            ));
+        }
+
+        project.check_exists()?;
+        T::create_folders(&project.data)?;
+
+        let previous = std::mem::replace(&mut self.config.project, project);
+
+        if let Err(e) = self.load_project(extras) {
+            self.config.project = previous;
+            return Err(e);
+        }
+
+        self.config
+            .project
+            .remember(&mut self.config.recent_projects);
+        self.config.save(self.config_file)
+    }
+
+    fn refresh_backups(&mut self) {
+        if self.backups.is_some() {
+            self.backups = Some(T::backups(&self.config.project.data, self.currentid));
+            self.backup_error = None;
+        }
+    }
+
+    /// Settles the first `count` conflicts by keeping the given side.
+    fn resolve_conflicts(&mut self, keep: DataSource, count: usize) {
+        let count = count.min(self.load_report.conflicts.len());
+        let conflicts: Vec<_> = self.load_report.conflicts.drain(..count).collect();
+
+        for conflict in conflicts {
+            match T::resolve_conflict(&self.config.project.data, conflict.id, keep) {
+                Ok(data) => {
+                    self.history.forget(conflict.id);
+                    self.data[conflict.id] = (data, false);
+                }
+                Err(e) => warn!("Could not resolve {}, err {}", conflict, e),
+            }
+        }
+
+        self.refresh = true;
+        self.relist = true;
+    }
+
+    /// Questions the editor asks before anything else: closing with unsaved
+    /// changes, crash recovery, failed saves, load problems and bin and json
+    /// conflicts.
+    pub fn notices_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        stack([
+            self.close_layout(),
+            self.recovery_layout(),
+            self.save_errors_layout(),
+            self.load_report_layout(),
+            self.conflicts_layout(),
+        ])
+    }
+
+    /// Buttons of the shared tools.
+    pub fn menu_layout(&self) -> Element<'_, EditorMessage> {
+        let unsaved = self.unsaved();
+
+        row![
+            button("Add").on_press_maybe(self.can_add().then_some(EditorMessage::AddRecord)),
+            button("Delete Last")
+                .on_press_maybe((self.data.len() > 1).then_some(EditorMessage::DeleteLastRecord)),
+            button("Revert").on_press(EditorMessage::RevertButtonPress),
+            button("Backups").on_press(EditorMessage::ShowBackups),
+            button("Projects").on_press(EditorMessage::Project(ProjectMessage::Show)),
+            button("References").on_press(EditorMessage::CheckReferences),
+        ]
+        .push_maybe(
+            self.tools
+                .sprites
+                .then(|| button("Sprites").on_press(EditorMessage::AuditSprites)),
+        )
+        .push(button("Bulk Edit").on_press(EditorMessage::BulkEdit(BulkEditMessage::Show)))
+        .push(button("Record Tools").on_press(EditorMessage::RecordTools(RecordToolsMessage::Show)))
+        .push_maybe(
+            self.tools
+                .referenced
+                .then(|| button("Used By").on_press(EditorMessage::ShowUsedBy)),
+        )
+        .push_maybe(
+            self.tools.referenced.then(|| {
+                button("Renumber").on_press(EditorMessage::Renumber(RenumberMessage::Show))
+            }),
+        )
+        .push(button("Save").on_press(EditorMessage::SaveButtonPress))
+        .push(button("Save All").on_press(EditorMessage::SaveAllButtonPress))
+        .push(
+            button(text(format!("{} Unsaved", unsaved)))
+                .on_press_maybe((unsaved > 0).then_some(EditorMessage::ShowUnsaved)),
+        )
+        .spacing(5)
+        .into()
+    }
+
+    /// Panels of the shared tools that are open.
+    pub fn panels_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        stack([
+            self.unsaved_layout(),
+            self.references_layout(),
+            self.sprite_audit_layout(),
+            self.used_by_layout(),
+            self.renumber
+                .view(self.data.len())
+                .map(|view| view.map(EditorMessage::Renumber)),
+            self.projects_layout(),
+            self.backups_layout(),
+            self.bulk_editor
+                .view(self.records.marked.len())
+                .map(|editor| editor.map(EditorMessage::BulkEdit)),
+            self.record_tools
+                .view(self.currentid, self.data.len())
+                .map(|tools| tools.map(EditorMessage::RecordTools)),
+        ])
+    }
+
+    /// The searchable list of records.
+    pub fn list_layout(&self) -> Element<'_, EditorMessage> {
+        self.records
+            .view(&self.list, self.currentid)
+            .map(EditorMessage::RecordList)
+    }
+
+    fn projects_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        let recent = self
+            .config
+            .recent_projects
+            .iter()
+            .map(|project| project.to_string())
+            .collect();
+
+        self.projects
+            .view(self.config.project.to_string(), recent)
+            .map(|view| view.map(EditorMessage::Project))
+    }
+
+    fn backups_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        let backups = self.backups.as_ref()?;
+
+        let rows = self
+            .backup_error
+            .iter()
+            .map(ActionRow::new)
+            .chain(backups.iter().enumerate().map(|(i, backup)| {
+                ActionRow::new(backup.label()).action("Restore", EditorMessage::RestoreBackup(i))
+            }))
+            .collect();
+
+        Some(action_list(
+            &format!("Previous versions of {} {}", T::NAME, self.currentid),
+            rows,
+            EditorMessage::CloseBackups,
+        ))
+    }
+
+    fn conflicts_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        let conflict = self.load_report.conflicts.first()?;
+
+        let rows = conflict
+            .differences
+            .iter()
+            .map(|difference| {
+                (
+                    difference.field.clone(),
+                    difference.bin.to_string(),
+                    difference.json.to_string(),
+                )
+            })
+            .collect();
+
+        Some(compare_view(
+            &format!(
+                "{} {} differs between bin and json ({} conflicts left)",
+                T::NAME,
+                conflict.id,
+                self.load_report.conflicts.len()
+            ),
+            ("Bin", "Json"),
+            rows,
+            vec![
+                ("Use Bin", EditorMessage::ResolveConflict(DataSource::Bin)),
+                ("Use Json", EditorMessage::ResolveConflict(DataSource::Json)),
+                (
+                    "All Bin",
+                    EditorMessage::ResolveAllConflicts(DataSource::Bin),
+                ),
+                (
+                    "All Json",
+                    EditorMessage::ResolveAllConflicts(DataSource::Json),
+                ),
+            ],
+        ))
+    }
+
+    fn recovery_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        let recovery = self.recovery.as_ref()?;
+
+        Some(confirm(
+            &format!("Recovered {}s", T::NAME),
+            format!(
+                "{} unsaved {}s from {} were kept when the editor last closed unexpectedly. Restore them?",
+                recovery.records.len(),
+                Self::noun(),
+                recovery.time.format("%Y-%m-%d %H:%M:%S")
+            ),
+            vec![
+                ("Restore", EditorMessage::RestoreRecovery),
+                ("Discard", EditorMessage::DiscardRecovery),
+            ],
+        ))
+    }
+
+    fn sprite_audit_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        let audit = self.sprite_audit.as_ref()?;
+
+        let rows = audit
+            .problems
+            .iter()
+            .map(|problem| {
+                let row = ActionRow::new(problem.to_string());
+
+                match problem.owner() {
+                    Some(owner) if owner.kind == T::KIND && owner.id < self.data.len() => {
+                        row.action("Go to", EditorMessage::GoToRecord(owner.id))
+                    }
+                    _ => row,
+                }
+            })
+            .chain(audit.notes().into_iter().map(ActionRow::new))
+            .collect();
+
+        Some(action_list(
+            &format!(
+                "{} Sprite Problems within {} images",
+                audit.problems.len(),
+                audit.images
+            ),
+            rows,
+            EditorMessage::CloseSpriteAudit,
+        ))
+    }
+
+    fn references_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        let references = self.references.as_ref()?;
+
+        let rows = references
+            .iter()
+            .map(|issue| {
+                let row = ActionRow::new(issue.to_string());
+
+                // Go to whichever side of the reference lives in this editor.
+                let record = [issue.owner, issue.target]
+                    .into_iter()
+                    .find(|record| record.kind == T::KIND && record.id < self.data.len());
+
+                match record {
+                    Some(record) => row.action("Go to", EditorMessage::GoToRecord(record.id)),
+                    None => row,
+                }
+            })
+            .collect();
+
+        Some(action_list(
+            &format!("{} Reference Problems", references.len()),
+            rows,
+            EditorMessage::CloseReferences,
+        ))
+    }
+
+    fn used_by_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        let index = self.used_by.as_ref()?;
+        let record = RecordRef::new(T::KIND, self.currentid);
+
+        let rows = index
+            .used_by(record)
+            .iter()
+            .map(|reference| {
+                let row = ActionRow::new(format!(
+                    "{} {} ({})",
+                    reference.owner,
+                    reference.field,
+                    index.name_of(reference.owner)
+                ));
+
+                if reference.owner.kind == T::KIND {
+                    row.action("Go to", EditorMessage::GoToRecord(reference.owner.id))
+                } else {
+                    row
+                }
+            })
+            .collect();
+
+        Some(action_list(
+            &format!("{} Used By", record),
+            rows,
+            EditorMessage::CloseUsedBy,
+        ))
+    }
+
+    fn close_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        self.close_request?;
+
+        Some(confirm(
+            "Unsaved Changes",
+            format!(
+                "{} records have unsaved changes. Save them before closing?",
+                self.unsaved()
+            ),
+            vec![
+                ("Save All and Close", EditorMessage::SaveAndClose),
+                ("Close Without Saving", EditorMessage::DiscardAndClose),
+                ("Cancel", EditorMessage::CancelClose),
+            ],
+        ))
+    }
+
+    fn unsaved_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        if !self.show_unsaved {
+            return None;
+        }
+
+        let rows = self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(_, data)| data.1)
+            .map(|(i, _)| {
+                ActionRow::new(self.list[i].to_string())
+                    .action("Save", EditorMessage::SaveRecord(i))
+                    .action("Discard", EditorMessage::DiscardRecord(i))
+            })
+            .collect();
+
+        Some(action_list(
+            &format!("Unsaved {}s", T::NAME),
+            rows,
+            EditorMessage::CloseUnsaved,
+        ))
+    }
+
+    fn save_errors_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        if self.save_errors.is_empty() {
+            return None;
+        }
+
+        Some(notice(
+            &format!("{} records could not be saved", self.save_errors.len()),
+            self.save_errors.clone(),
+            EditorMessage::DismissSaveErrors,
+        ))
+    }
+
+    fn load_report_layout(&self) -> Option<Element<'_, EditorMessage>> {
+        if self.load_report.is_empty() {
+            return None;
+        }
+
+        Some(notice(
+            &format!("Recovered {}s", T::NAME),
+            self.load_report.lines(),
+            EditorMessage::DismissLoadReport,
+        ))
+    }
+}
+
+/// The given views one below the other, or nothing if none are shown.
+fn stack<'a, const N: usize>(
+    views: [Option<Element<'a, EditorMessage>>; N],
+) -> Option<Element<'a, EditorMessage>> {
+    let views: Vec<_> = views.into_iter().flatten().collect();
+
+    if views.is_empty() {
+        return None;
+    }
+
+    Some(Column::with_children(views).spacing(20).into())
+}
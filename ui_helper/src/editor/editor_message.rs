@@ -0,0 +1,46 @@
+use crate::*;
+use ascending_data::*;
+use iced::window;
+
+/// Messages every record editor handles the same way, through
+/// `EditorCore::update`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorMessage {
+    SaveButtonPress,
+    SaveAllButtonPress,
+    RevertButtonPress,
+    AddRecord,
+    DeleteLastRecord,
+    DismissLoadReport,
+    DismissSaveErrors,
+    History(HistoryMessage),
+    CloseRequested(window::Id),
+    Autosave,
+    RestoreRecovery,
+    DiscardRecovery,
+    SaveAndClose,
+    DiscardAndClose,
+    CancelClose,
+    CheckReferences,
+    CloseReferences,
+    AuditSprites,
+    CloseSpriteAudit,
+    GoToRecord(usize),
+    ShowUsedBy,
+    CloseUsedBy,
+    Renumber(RenumberMessage),
+    ShowUnsaved,
+    CloseUnsaved,
+    SaveRecord(usize),
+    DiscardRecord(usize),
+    ResolveConflict(DataSource),
+    ResolveAllConflicts(DataSource),
+    Project(ProjectMessage),
+    ShowBackups,
+    CloseBackups,
+    RestoreBackup(usize),
+    RecordList(RecordListMessage),
+    RecordTools(RecordToolsMessage),
+    PasteRecord(Option<String>),
+    BulkEdit(BulkEditMessage),
+}
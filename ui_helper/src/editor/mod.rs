@@ -0,0 +1,7 @@
+mod editor_config;
+mod editor_core;
+mod editor_message;
+
+pub use editor_config::*;
+pub use editor_core::*;
+pub use editor_message::*;
@@ -1,5 +1,7 @@
 pub mod controls;
+pub mod editor;
 pub mod ui_controls;
 
 pub use controls::*;
+pub use editor::*;
 pub use ui_controls::*;
@@ -1,7 +1,7 @@
-use iced::Element;
+use iced::{Element, Task};
 
 pub trait UiRenderer {
     type Message;
-    fn update(&mut self, msg: Self::Message);
-    fn view(&self) -> Element<Self::Message>;
+    fn update(&mut self, msg: Self::Message) -> Task<Self::Message>;
+    fn view(&self) -> Element<'_, Self::Message>;
 }